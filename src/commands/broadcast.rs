@@ -1,32 +1,64 @@
 use anyhow::{Context, Result};
 use ethers::{
     providers::{Http, Middleware, Provider},
-    types::Bytes,
+    types::{Address, Bytes},
 };
 use std::fs;
 
-use crate::types::sign_output::SignedTransaction;
+use super::inspect;
+use crate::types::sign_output::{SignedBundle, SignedTransaction};
 
-pub async fn execute(signed_path: String) -> Result<()> {
+pub async fn execute(signed_path: String, rpc_override: Option<String>) -> Result<()> {
     println!("Broadcasting transaction...");
     println!("Loading signed transaction from: {}", signed_path);
 
-    // Load signed transaction
+    // Load signed transaction, or a bundle of them
     let signed_json = fs::read_to_string(&signed_path)
         .context("Failed to read signed transaction file")?;
 
-    let signed_tx: SignedTransaction = serde_json::from_str(&signed_json)
+    let signed_value: serde_json::Value = serde_json::from_str(&signed_json)
         .context("Failed to parse signed transaction JSON")?;
+    let bundle: SignedBundle = if signed_value.get("transactions").is_some() {
+        serde_json::from_value(signed_value).context("Failed to parse signed transaction bundle")?
+    } else {
+        let signed_tx: SignedTransaction = serde_json::from_value(signed_value)
+            .context("Failed to parse signed transaction JSON")?;
 
-    // Validate transaction hash matches what's expected
-    println!("Transaction hash: {}", signed_tx.transaction_hash);
-    println!("From: {}", signed_tx.from);
-    println!("Nonce: {}", signed_tx.nonce);
+        // Validate transaction hash matches what's expected
+        println!("Transaction hash: {}", signed_tx.transaction_hash);
+        println!("From: {}", signed_tx.from);
+        println!("Nonce: {}", signed_tx.nonce);
+        if let Some(ref address) = signed_tx.contract_address {
+            println!("Expected contract address: {}", address);
+        }
+
+        SignedBundle {
+            chain_id: signed_tx.chain_id,
+            from: signed_tx.from.clone(),
+            rpc_url: signed_tx.rpc_url.clone(),
+            transactions: vec![signed_tx],
+        }
+    };
+    let count = bundle.transactions.len();
+    if count > 1 {
+        println!("Bundle of {} transactions from {}", count, bundle.from);
+    }
 
-    // Use RPC URL from signed transaction
-    let rpc_url = &signed_tx.rpc_url;
+    // A spliced or edited file must fail before anything is sent
+    let mismatches = inspect::bundle_mismatches(&bundle)?;
+    if !mismatches.is_empty() {
+        anyhow::bail!(
+            "The signed transactions do not match the file; nothing was sent:\n  {}",
+            mismatches.join("\n  ")
+        );
+    }
+
+    // Use the RPC URL given on the command line, else the one stored in the signed transaction
+    let rpc_url = rpc_override
+        .or_else(|| bundle.rpc_url.clone())
+        .context("No RPC URL stored in the signed transaction; pass --rpc-url or --network with --infura-key")?;
     println!("\nConnecting to RPC: {}", rpc_url);
-    let provider = Provider::<Http>::try_from(rpc_url)
+    let provider = Provider::<Http>::try_from(rpc_url.as_str())
         .context("Failed to create provider")?;
 
     // Verify chain ID matches
@@ -36,15 +68,39 @@ pub async fn execute(signed_path: String) -> Result<()> {
         .context("Failed to fetch chain ID from RPC")?
         .as_u64();
 
-    if rpc_chain_id != signed_tx.chain_id {
+    if rpc_chain_id != bundle.chain_id {
         anyhow::bail!(
             "Chain ID mismatch! Transaction signed for chain {} but RPC is on chain {}",
-            signed_tx.chain_id,
+            bundle.chain_id,
             rpc_chain_id
         );
     }
     println!("Chain ID verified: {}", rpc_chain_id);
 
+    // Later transactions may depend on earlier ones, so each is confirmed before the next
+    for (i, signed_tx) in bundle.transactions.iter().enumerate() {
+        if count > 1 {
+            println!("\n── Transaction {}/{} (nonce {}) ──", i + 1, count, signed_tx.nonce);
+        }
+        if !send_transaction(&provider, signed_tx).await? {
+            match count - i - 1 {
+                0 if count == 1 => anyhow::bail!("Transaction did not succeed"),
+                0 => anyhow::bail!("Transaction {} did not succeed", i + 1),
+                remaining => anyhow::bail!(
+                    "Transaction {} did not succeed; the remaining {} transaction(s) were not sent",
+                    i + 1,
+                    remaining
+                ),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Send one signed transaction and wait for its receipt. Returns whether it was mined
+/// successfully.
+async fn send_transaction(provider: &Provider<Http>, signed_tx: &SignedTransaction) -> Result<bool> {
     // Decode raw transaction
     let raw_tx = signed_tx.raw_transaction.strip_prefix("0x")
         .unwrap_or(&signed_tx.raw_transaction);
@@ -64,25 +120,63 @@ pub async fn execute(signed_path: String) -> Result<()> {
     println!("\nWaiting for transaction confirmation...");
     match pending_tx.await {
         Ok(Some(receipt)) => {
+            let succeeded = receipt.status.is_some_and(|status| status.as_u64() == 1);
             println!("\n✓ Transaction confirmed!");
             println!("  Block number: {}", receipt.block_number.unwrap());
             println!("  Gas used: {}", receipt.gas_used.unwrap());
-            println!("  Status: {}", if receipt.status.unwrap().as_u64() == 1 { "Success" } else { "Failed" });
+            println!("  Status: {}", if succeeded { "Success" } else { "Failed" });
 
             // If contract deployment, show contract address
             if let Some(contract_address) = receipt.contract_address {
                 println!("\n✓ Contract deployed!");
                 println!("  Contract address: {:?}", contract_address);
             }
+
+            // Compare with the address predicted at prepare time
+            if let Some(ref expected) = signed_tx.contract_address {
+                if succeeded {
+                    verify_contract_address(provider, expected, receipt.contract_address).await?;
+                }
+            }
+            Ok(succeeded)
         }
         Ok(None) => {
             println!("\n⚠ Transaction was dropped from the mempool");
+            Ok(false)
         }
         Err(e) => {
             println!("\n✗ Transaction failed: {:?}", e);
             anyhow::bail!("Transaction failed");
         }
     }
+}
 
+/// Check the deployment landed at the predicted address. CREATE deployments report it in
+/// the receipt; CREATE2 deployments go through a factory, so the code is looked up instead.
+async fn verify_contract_address(
+    provider: &Provider<Http>,
+    expected: &str,
+    deployed: Option<Address>,
+) -> Result<()> {
+    let expected: Address = expected.parse().context("Invalid contract address in signed transaction")?;
+    match deployed {
+        Some(deployed) if deployed != expected => anyhow::bail!(
+            "Contract was deployed at {:?}, but {:?} was predicted",
+            deployed,
+            expected
+        ),
+        Some(_) => println!("  ✓ Matches the predicted address"),
+        None => {
+            let code = provider
+                .get_code(expected, None)
+                .await
+                .context("Failed to fetch code at the predicted contract address")?;
+            if code.is_empty() {
+                anyhow::bail!("No contract code at the predicted address {:?}", expected);
+            }
+            println!("\n✓ Contract deployed!");
+            println!("  Contract address: {:?} (predicted)", expected);
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,18 @@
+use anyhow::{Context, Result};
+
+use crate::utils::review;
+
+pub async fn execute(calldata: String, abi_path: Option<String>) -> Result<()> {
+    let hex_data = calldata.trim();
+    let data = hex::decode(hex_data.strip_prefix("0x").unwrap_or(hex_data))
+        .context("Invalid calldata: expected hex")?;
+    let abi = abi_path.as_deref().map(review::load_abi).transpose()?;
+
+    println!("Calldata: {} bytes", data.len());
+    if let Some(selector) = data.get(..4) {
+        println!("Selector: 0x{}", hex::encode(selector));
+    }
+    review::print_call(&data, abi.as_ref());
+
+    Ok(())
+}
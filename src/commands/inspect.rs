@@ -0,0 +1,422 @@
+use anyhow::{Context, Result};
+use ethers::{
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, H256, U256},
+    utils::{get_contract_address, keccak256, rlp::Rlp, to_checksum},
+};
+use std::fs;
+use std::path::Path;
+
+use super::sign;
+use crate::types::prepare_output::{UnsignedBundle, UnsignedTransaction};
+use crate::types::sign_output::{SignedBundle, SignedTransaction};
+use crate::utils::review::{self, ReviewAbi};
+use crate::utils::typed_tx::{self, SignedAuthorization, BLOB_TX_TYPE, SET_CODE_TX_TYPE};
+use crate::utils::{chains, units};
+
+/// A signed transaction decoded from its raw encoding, whatever its type
+struct Decoded {
+    kind: &'static str,
+    /// Absent for legacy transactions signed without EIP-155 replay protection
+    chain_id: Option<u64>,
+    nonce: u64,
+    to: Option<Address>,
+    value: U256,
+    gas_limit: U256,
+    gas_price: Option<U256>,
+    max_fee_per_gas: Option<U256>,
+    max_priority_fee_per_gas: Option<U256>,
+    access_list_entries: usize,
+    max_fee_per_blob_gas: Option<U256>,
+    blob_versioned_hashes: Vec<H256>,
+    authorization_list: Vec<SignedAuthorization>,
+    data: Bytes,
+    /// Signer recovered from the signature
+    from: Address,
+    /// Hash recomputed from the encoding
+    hash: H256,
+}
+
+pub async fn execute(input: String, abi_path: Option<String>) -> Result<()> {
+    let abi = abi_path.as_deref().map(review::load_abi).transpose()?;
+
+    // A raw transaction given on the command line rather than a file
+    let input = input.trim();
+    if let Some(hex_data) = input.strip_prefix("0x").filter(|_| !Path::new(input).exists()) {
+        let raw = hex::decode(hex_data).context("Invalid raw transaction: expected hex")?;
+        let decoded = decode_raw(&raw)?;
+        return print_decoded(&decoded, abi.as_ref());
+    }
+
+    println!("Inspecting: {}", input);
+    let content = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read transaction file: {}", input))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .context("Failed to parse transaction JSON")?;
+
+    let is_signed = |tx: &serde_json::Value| tx.get("raw_transaction").is_some();
+    match value.get("transactions").and_then(|txs| txs.get(0)) {
+        Some(first) if is_signed(first) => {
+            let bundle: SignedBundle = serde_json::from_value(value)
+                .context("Failed to parse signed transaction bundle")?;
+            inspect_signed_bundle(&bundle, abi.as_ref())
+        }
+        Some(_) => {
+            let bundle: UnsignedBundle = serde_json::from_value(value)
+                .context("Failed to parse unsigned transaction bundle")?;
+            sign::check_bundle(&bundle)?;
+            println!("Unsigned bundle of {} transactions", bundle.transactions.len());
+            for (i, unsigned_tx) in bundle.transactions.iter().enumerate() {
+                println!("\nTransaction {}/{} (nonce {})", i + 1, bundle.transactions.len(), unsigned_tx.nonce);
+                inspect_unsigned(unsigned_tx, abi.as_ref())?;
+            }
+            Ok(())
+        }
+        None if value.get("transactions").is_some() => anyhow::bail!("Bundle contains no transactions"),
+        None if is_signed(&value) => {
+            let signed_tx: SignedTransaction = serde_json::from_value(value)
+                .context("Failed to parse signed transaction JSON")?;
+            let mismatches = inspect_signed(&signed_tx, abi.as_ref())?;
+            if mismatches > 0 {
+                anyhow::bail!(
+                    "{} field(s) of the signed file do not match its raw transaction; do not broadcast it",
+                    mismatches
+                );
+            }
+            Ok(())
+        }
+        None => {
+            let unsigned_tx: UnsignedTransaction = serde_json::from_value(value)
+                .context("Failed to parse unsigned transaction JSON")?;
+            println!("Unsigned transaction");
+            inspect_unsigned(&unsigned_tx, abi.as_ref())
+        }
+    }
+}
+
+/// Run the checks `sign` makes before asking for the password, and show its review
+fn inspect_unsigned(unsigned_tx: &UnsignedTransaction, abi: Option<&ReviewAbi>) -> Result<()> {
+    sign::check_unsigned(unsigned_tx)?;
+    review::print_review(unsigned_tx, abi)
+}
+
+fn inspect_signed_bundle(bundle: &SignedBundle, abi: Option<&ReviewAbi>) -> Result<()> {
+    let count = bundle.transactions.len();
+    println!("Signed bundle of {} transactions from {}", count, bundle.from);
+
+    let mut mismatches = 0;
+    for (i, signed_tx) in bundle.transactions.iter().enumerate() {
+        println!("\n── Transaction {}/{} (nonce {}) ──", i + 1, count, signed_tx.nonce);
+        mismatches += inspect_signed(signed_tx, abi)?;
+    }
+    let bundle_mismatches = bundle_mismatches(bundle)?;
+    if !bundle_mismatches.is_empty() {
+        println!();
+    }
+    for mismatch in &bundle_mismatches {
+        println!("  ⚠ {}", mismatch);
+    }
+    mismatches += bundle_mismatches.len();
+
+    if mismatches > 0 {
+        anyhow::bail!("{} mismatch(es) found in the signed bundle; do not broadcast it", mismatches);
+    }
+    println!("\n✓ All {} transactions match their raw encoding", count);
+    Ok(())
+}
+
+/// Broadcast reads the chain from the bundle and sends the transactions in order, so each
+/// raw transaction must be for the bundle's chain and sender, following the previous nonce.
+/// Returns one line per problem
+pub fn bundle_mismatches(bundle: &SignedBundle) -> Result<Vec<String>> {
+    if bundle.transactions.is_empty() {
+        return Ok(vec!["Bundle contains no transactions".to_string()]);
+    }
+    let from: Address = bundle.from.parse().context("Invalid from address in the bundle")?;
+    let mut mismatches = Vec::new();
+    let mut first_nonce = None;
+    for (i, signed_tx) in bundle.transactions.iter().enumerate() {
+        let raw = hex::decode(signed_tx.raw_transaction.trim_start_matches("0x"))
+            .with_context(|| format!("Invalid raw transaction {}: expected hex", i + 1))?;
+        let decoded = decode_raw(&raw).with_context(|| format!("Transaction {} does not decode", i + 1))?;
+        if decoded.chain_id != Some(bundle.chain_id) {
+            mismatches.push(format!(
+                "Transaction {} is signed for chain {}, but the bundle is for chain {}",
+                i + 1,
+                decoded.chain_id.map_or("(none)".to_string(), |id| id.to_string()),
+                bundle.chain_id
+            ));
+        }
+        if decoded.from != from {
+            mismatches.push(format!(
+                "Transaction {} is signed by {}, but the bundle is from {}",
+                i + 1,
+                checksum(&decoded.from),
+                checksum(&from)
+            ));
+        }
+        let expected = *first_nonce.get_or_insert(decoded.nonce) + i as u64;
+        if decoded.nonce != expected {
+            mismatches.push(format!(
+                "Transaction {} has nonce {}, not {}; bundle nonces must be consecutive",
+                i + 1,
+                decoded.nonce,
+                expected
+            ));
+        }
+    }
+    Ok(mismatches)
+}
+
+/// Decode the raw transaction of a signed file and compare it with the file's fields;
+/// returns the number of fields that do not match
+fn inspect_signed(signed_tx: &SignedTransaction, abi: Option<&ReviewAbi>) -> Result<usize> {
+    let hex_data = signed_tx.raw_transaction.trim_start_matches("0x");
+    let raw = hex::decode(hex_data).context("Invalid raw transaction: expected hex")?;
+    let decoded = decode_raw(&raw)?;
+    print_decoded(&decoded, abi)?;
+
+    // Broadcast trusts these fields, so each must say what the signed bytes say
+    let mut mismatches = Vec::new();
+    let from: Address = signed_tx.from.parse().context("Invalid from address")?;
+    if from != decoded.from {
+        mismatches.push(format!("From: file has {}, signature recovers {}", checksum(&from), checksum(&decoded.from)));
+    }
+    let to = signed_tx
+        .to
+        .as_deref()
+        .map(str::parse::<Address>)
+        .transpose()
+        .context("Invalid to address")?;
+    if to != decoded.to {
+        mismatches.push(format!("To: file has {}, transaction has {}", describe_to(to), describe_to(decoded.to)));
+    }
+    if signed_tx.nonce != decoded.nonce {
+        mismatches.push(format!("Nonce: file has {}, transaction has {}", signed_tx.nonce, decoded.nonce));
+    }
+    if Some(signed_tx.chain_id) != decoded.chain_id {
+        let chain_id = decoded.chain_id.map_or("none".to_string(), |id| id.to_string());
+        mismatches.push(format!("Chain ID: file has {}, transaction has {}", signed_tx.chain_id, chain_id));
+    }
+    let hash: H256 = signed_tx.transaction_hash.parse().context("Invalid transaction hash")?;
+    if hash != decoded.hash {
+        mismatches.push(format!("Transaction hash: file has {:?}, encoding hashes to {:?}", hash, decoded.hash));
+    }
+    // Broadcast checks the deployed contract against this address
+    if let (None, Some(address)) = (decoded.to, &signed_tx.contract_address) {
+        let address: Address = address.parse().context("Invalid contract address")?;
+        let expected = get_contract_address(decoded.from, decoded.nonce);
+        if address != expected {
+            mismatches.push(format!("Contract address: file has {}, deployment creates {}", checksum(&address), checksum(&expected)));
+        }
+    }
+
+    if mismatches.is_empty() {
+        println!("✓ from, to, nonce, chain ID and hash match the raw transaction");
+    } else {
+        for mismatch in &mismatches {
+            println!("⚠ {}", mismatch);
+        }
+    }
+    Ok(mismatches.len())
+}
+
+/// Decode a signed legacy, EIP-2930, EIP-1559, blob or set-code transaction, recover its
+/// signer and recompute its hash
+fn decode_raw(raw: &[u8]) -> Result<Decoded> {
+    match raw.first() {
+        None => anyhow::bail!("Raw transaction is empty"),
+        Some(&BLOB_TX_TYPE) | Some(&SET_CODE_TX_TYPE) => {
+            let tx = typed_tx::decode_signed(raw).context("Failed to decode raw transaction")?;
+            let from = tx
+                .signature
+                .recover(tx.sighash)
+                .context("Failed to recover the signer")?;
+            Ok(Decoded {
+                kind: if tx.tx_type == BLOB_TX_TYPE { "EIP-4844 blob (type 3)" } else { "EIP-7702 set code (type 4)" },
+                chain_id: Some(tx.chain_id),
+                nonce: tx.nonce,
+                to: Some(tx.to),
+                value: tx.value,
+                gas_limit: U256::from(tx.gas_limit),
+                gas_price: None,
+                max_fee_per_gas: Some(tx.max_fee_per_gas),
+                max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+                access_list_entries: tx.access_list.0.len(),
+                max_fee_per_blob_gas: tx.max_fee_per_blob_gas,
+                blob_versioned_hashes: tx.blob_versioned_hashes,
+                authorization_list: tx.authorization_list,
+                data: tx.data,
+                from,
+                hash: tx.hash,
+            })
+        }
+        Some(_) => {
+            let (tx, signature) = TypedTransaction::decode_signed(&Rlp::new(raw))
+                .map_err(|e| anyhow::anyhow!("Failed to decode raw transaction: {}", e))?;
+            // Decoding stops at the end of the transaction, so re-encode to reject trailing
+            // or non-canonical bytes that the hash would cover
+            if tx.rlp_signed(&signature).as_ref() != raw {
+                anyhow::bail!("Raw transaction is not canonically encoded");
+            }
+            let from = signature
+                .recover(tx.sighash())
+                .context("Failed to recover the signer")?;
+            let (kind, gas_price, max_fee_per_gas, max_priority_fee_per_gas) = match &tx {
+                TypedTransaction::Legacy(t) => ("legacy (type 0)", t.gas_price, None, None),
+                TypedTransaction::Eip2930(t) => ("EIP-2930 (type 1)", t.tx.gas_price, None, None),
+                TypedTransaction::Eip1559(t) => {
+                    ("EIP-1559 (type 2)", None, t.max_fee_per_gas, t.max_priority_fee_per_gas)
+                }
+            };
+            let nonce = tx.nonce().copied().unwrap_or_default();
+            Ok(Decoded {
+                kind,
+                chain_id: tx.chain_id().map(|id| id.as_u64()),
+                nonce: u64::try_from(nonce).map_err(|_| anyhow::anyhow!("Nonce {} does not fit in 64 bits", nonce))?,
+                to: tx.to_addr().copied(),
+                value: tx.value().copied().unwrap_or_default(),
+                gas_limit: tx.gas().copied().unwrap_or_default(),
+                gas_price,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                access_list_entries: tx.access_list().map_or(0, |list| list.0.len()),
+                max_fee_per_blob_gas: None,
+                blob_versioned_hashes: Vec::new(),
+                authorization_list: Vec::new(),
+                data: tx.data().cloned().unwrap_or_default(),
+                from,
+                hash: H256::from(keccak256(raw)),
+            })
+        }
+    }
+}
+
+fn print_decoded(tx: &Decoded, abi: Option<&ReviewAbi>) -> Result<()> {
+    let symbol = tx.chain_id.map_or("ETH", chains::native_symbol);
+    let max_fee = review::fee_ceiling(
+        tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default(),
+        tx.gas_limit,
+        tx.max_fee_per_blob_gas.unwrap_or_default(),
+        tx.blob_versioned_hashes.len(),
+    )?;
+    let max_cost = max_fee
+        .checked_add(tx.value)
+        .context("Max total cost overflows 256 bits; the value is invalid")?;
+
+    println!("\n═══════════════════ SIGNED TRANSACTION ═══════════════════");
+    println!("  Type:           {}", tx.kind);
+    match tx.chain_id {
+        Some(chain_id) => println!("  Chain:          {}", chains::describe(chain_id)),
+        None => println!("  Chain:          ⚠ none (no EIP-155 replay protection; valid on every chain)"),
+    }
+    println!("  From:           {} (recovered from the signature)", checksum(&tx.from));
+    println!("  Nonce:          {}", tx.nonce);
+    println!("  To:             {}", describe_to(tx.to));
+    println!("  Value:          {} {}", units::format_ether(tx.value), symbol);
+    println!("  Gas limit:      {}", tx.gas_limit);
+    match (tx.max_fee_per_gas, tx.gas_price) {
+        (Some(max_fee_per_gas), _) => println!(
+            "  Max fee:        {} gwei per gas (priority fee {} gwei)",
+            units::format_gwei(max_fee_per_gas),
+            units::format_gwei(tx.max_priority_fee_per_gas.unwrap_or_default())
+        ),
+        (None, Some(gas_price)) => println!("  Gas price:      {} gwei", units::format_gwei(gas_price)),
+        (None, None) => {}
+    }
+    if let Some(max_fee_per_blob_gas) = tx.max_fee_per_blob_gas {
+        println!(
+            "  Blobs:          {} (max blob fee {} gwei per blob gas)",
+            tx.blob_versioned_hashes.len(),
+            units::format_gwei(max_fee_per_blob_gas)
+        );
+    }
+    println!("  Fee ceiling:    {} {}", units::format_ether(max_fee), symbol);
+    println!("  Max total cost: {} {}", units::format_ether(max_cost), symbol);
+    if tx.access_list_entries > 0 {
+        println!("  Access list:    {} address(es)", tx.access_list_entries);
+    }
+    for authorization in &tx.authorization_list {
+        let authority = typed_tx::authorization_sighash(authorization.chain_id, authorization.address, authorization.nonce);
+        let authority = match authorization.signature.recover(authority) {
+            Ok(address) => checksum(&address),
+            Err(_) => "invalid signature".to_string(),
+        };
+        println!(
+            "  Delegation:     {} delegates to {} (nonce {}, chain {})",
+            authority,
+            checksum(&authorization.address),
+            authorization.nonce,
+            authorization.chain_id
+        );
+    }
+    println!("  Hash:           {:?}", tx.hash);
+
+    println!("  ──────────────────────────────────────────────────────────");
+    if tx.to.is_none() {
+        println!("  Action:         deploy a contract");
+        println!("  Init code:      {} bytes", tx.data.len());
+        println!("  Contract at:    {}", checksum(&get_contract_address(tx.from, tx.nonce)));
+    } else if tx.data.is_empty() {
+        println!("  Action:         plain transfer");
+    } else {
+        review::print_call(&tx.data, abi);
+    }
+    println!("══════════════════════════════════════════════════════════");
+    Ok(())
+}
+
+fn describe_to(to: Option<Address>) -> String {
+    to.map_or("(contract deployment)".to_string(), |to| checksum(&to))
+}
+
+fn checksum(address: &Address) -> String {
+    to_checksum(address, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::Eip1559TransactionRequest;
+
+    fn sign(tx: Eip1559TransactionRequest) -> (Vec<u8>, Address) {
+        let wallet: LocalWallet = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let tx = TypedTransaction::Eip1559(tx.chain_id(1u64));
+        let signature = wallet.sign_transaction_sync(&tx).unwrap();
+        (tx.rlp_signed(&signature).to_vec(), wallet.address())
+    }
+
+    #[test]
+    fn decodes_a_signed_eip1559_transaction() {
+        let (raw, from) = sign(
+            Eip1559TransactionRequest::new()
+                .to(Address::repeat_byte(0x22))
+                .value(1000)
+                .nonce(7)
+                .gas(21000)
+                .max_fee_per_gas(30_000_000_000u64)
+                .max_priority_fee_per_gas(1_000_000_000u64),
+        );
+        let decoded = decode_raw(&raw).unwrap();
+        assert_eq!(decoded.from, from);
+        assert_eq!(decoded.nonce, 7);
+        assert_eq!(decoded.chain_id, Some(1));
+        assert_eq!(decoded.hash, H256::from(keccak256(&raw)));
+        print_decoded(&decoded, None).unwrap();
+
+        assert!(decode_raw(&[&raw[..], &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn overflowing_fee_ceiling_is_an_error() {
+        let (raw, _) = sign(
+            Eip1559TransactionRequest::new()
+                .to(Address::repeat_byte(0x22))
+                .gas(21000)
+                .max_fee_per_gas(U256::one() << 255)
+                .max_priority_fee_per_gas(0),
+        );
+        let decoded = decode_raw(&raw).unwrap();
+        assert!(print_decoded(&decoded, None).is_err());
+    }
+}
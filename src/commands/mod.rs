@@ -1,6 +1,10 @@
 pub mod prepare;
+pub mod prepare_bundle;
+pub mod prepare_forge;
 pub mod prepare_interactive;
 pub mod sign;
 pub mod broadcast;
+pub mod decode;
+pub mod inspect;
 pub mod generate_mnemonic;
 pub mod derive_key;
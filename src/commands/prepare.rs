@@ -1,27 +1,106 @@
 use anyhow::{Context, Result};
 use ethers::{
-    abi::{Abi, Token},
+    abi::{Abi, Function},
     providers::{Http, Middleware, Provider},
-    types::{H160, U256},
+    types::{H160, H256},
+    utils::get_contract_address,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
 use std::str::FromStr;
 
-use crate::types::prepare_output::UnsignedTransaction;
-use crate::utils::contract;
+use crate::constants::{DEFAULT_CREATE2_FACTORY, DEFAULT_MULTICALL3};
+use crate::types::prepare_output::{
+    Authorization, Create2Deployment, GasLimitSource, MulticallCall, UnsignedTransaction,
+};
+use crate::utils::fees::{self, FeeStrategy, Fees};
+use crate::utils::blob::{self, BlobEncoding, BlobProofs};
+use crate::utils::gas::{self, CallRequest};
+use crate::utils::simulate::{self, Simulation};
+use crate::utils::contract::{ContractArtifact, LibraryLink};
+use crate::utils::typed_tx::DELEGATION_DESIGNATOR_PREFIX;
+use crate::utils::{abi_args, contract, metadata, multicall, units};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrepareParams {
-    pub contract: String,
-    pub rpc_url: String,
+    pub contract: Option<String>,
+    /// Contract to use from a file holding several (`Name` or `path/File.sol:Name`)
+    #[serde(default)]
+    pub contract_name: Option<String>,
+    /// RPC endpoint; optional in offline mode, where it is only recorded for broadcast
+    pub rpc_url: Option<String>,
     pub from: String,
     pub to: Option<String>,
     pub function_name: Option<String>,
-    pub args: Option<String>,
+    /// Human-readable function signature, used instead of an artifact
+    #[serde(default)]
+    pub sig: Option<String>,
+    /// Raw calldata (hex), used instead of an artifact
+    #[serde(default)]
+    pub data: Option<String>,
+    #[serde(default)]
+    pub args: Vec<Value>,
     pub value: String,
     pub output: String,
     pub gas_limit: Option<u64>,
+    #[serde(default = "default_gas_multiplier")]
+    pub gas_multiplier: f64,
+    #[serde(default)]
+    pub fee_strategy: FeeStrategy,
+    /// Max fee per gas override (gwei unless a unit is given)
+    pub max_fee: Option<String>,
+    /// Max priority fee per gas override (gwei unless a unit is given)
+    pub priority_fee: Option<String>,
+    /// Legacy gas price override (gwei unless a unit is given)
+    pub gas_price: Option<String>,
+    /// Build the transaction without contacting the RPC
+    #[serde(default)]
+    pub offline: bool,
+    /// Chain ID (offline mode only)
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+    /// Account nonce (offline mode only)
+    #[serde(default)]
+    pub nonce: Option<u64>,
+    /// Write the transaction even if the pre-flight simulation reverts
+    #[serde(default)]
+    pub force: bool,
+    /// Attach an access list generated with `eth_createAccessList`
+    #[serde(default)]
+    pub access_list: bool,
+    /// Blob data files; makes an EIP-4844 transaction
+    #[serde(default)]
+    pub blobs: Vec<String>,
+    #[serde(default)]
+    pub blob_encoding: BlobEncoding,
+    #[serde(default)]
+    pub blob_proofs: BlobProofs,
+    /// Max fee per blob gas override (gwei unless a unit is given)
+    #[serde(default)]
+    pub max_blob_fee: Option<String>,
+    /// EIP-7702 delegate address; makes a set-code transaction
+    #[serde(default)]
+    pub delegate: Option<String>,
+    /// Deploy through a CREATE2 factory instead of a contract-creation transaction
+    #[serde(default)]
+    pub create2: bool,
+    /// CREATE2 salt (32 bytes of hex)
+    #[serde(default)]
+    pub salt: Option<String>,
+    /// CREATE2 factory; defaults to the deterministic deployment proxy
+    #[serde(default)]
+    pub factory: Option<String>,
+    /// Library addresses for linking, as `Name=0xAddress`
+    #[serde(default)]
+    pub links: Vec<String>,
+    /// File listing calls to batch through Multicall3 (`to` overrides the Multicall3 address)
+    #[serde(default)]
+    pub multicall: Option<String>,
+}
+
+fn default_gas_multiplier() -> f64 {
+    gas::DEFAULT_GAS_MULTIPLIER
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,300 +111,740 @@ pub struct PrepareResult {
 }
 
 pub async fn run(params: PrepareParams) -> Result<PrepareResult> {
+    let output = params.output.clone();
+    let unsigned_tx = build(params).await?;
+
+    // Save to output file
+    println!("Saving unsigned transaction to: {}", output);
+    let json = serde_json::to_string_pretty(&unsigned_tx)
+        .context("Failed to serialize transaction")?;
+
+    fs::write(&output, json).context("Failed to write output file")?;
+
+    println!("\n✓ Unsigned transaction prepared successfully!");
+    print_summary(&unsigned_tx);
+
+    let message = "Unsigned transaction prepared successfully!".to_string();
+
+    Ok(PrepareResult {
+        unsigned_tx,
+        success: true,
+        message,
+    })
+}
+
+/// Build the unsigned transaction described by `params` without writing it.
+///
+/// A `nonce` given outside offline mode is used instead of the account's next nonce,
+/// which lets a bundle assign consecutive nonces to its transactions.
+pub async fn build(params: PrepareParams) -> Result<UnsignedTransaction> {
     let contract_path = params.contract;
+    let contract_name = params.contract_name;
     let rpc_url = params.rpc_url;
     let from = params.from;
     let to = params.to;
     let function_name = params.function_name;
+    let sig = params.sig;
+    let raw_data = params.data;
     let args = params.args;
     let value = params.value;
-    let output = params.output;
     let gas_limit = params.gas_limit;
+    let gas_multiplier = params.gas_multiplier;
+    let fee_strategy = params.fee_strategy;
+    let max_fee = params.max_fee;
+    let priority_fee = params.priority_fee;
+    let gas_price = params.gas_price;
+    let offline = params.offline;
+    let chain_id = params.chain_id;
+    let nonce = params.nonce;
+    let force = params.force;
+    let use_access_list = params.access_list;
+    let blob_files = params.blobs;
+    let blob_encoding = params.blob_encoding;
+    let blob_proofs = params.blob_proofs;
+    let max_blob_fee = params.max_blob_fee;
+    let delegate = params.delegate;
+    let use_create2 = params.create2;
+    let salt = params.salt;
+    let factory = params.factory;
+    let links = params.links;
+    let multicall_path = params.multicall;
+
+    // Validate the value here so a malformed amount never reaches the signer
+    let value = units::parse_wei(&value)
+        .with_context(|| format!("Invalid --value: {}", value))?;
+
     println!("Preparing unsigned transaction...");
-    println!("Contract: {}", contract_path);
+    if let Some(path) = &contract_path {
+        println!("Contract: {}", path);
+    }
     println!("From: {}", from);
 
-    // Parse contract JSON to extract bytecode and ABI
-    let (bytecode, abi_value) = contract::parse_contract_json(&contract_path)
-        .context("Failed to parse contract JSON")?;
+    // Calldata for a call comes from an artifact function, a signature, or raw hex
+    let call = match (function_name.as_deref(), sig.as_deref(), raw_data.as_deref(), multicall_path.as_deref()) {
+        (None, None, None, None) => None,
+        (Some(name), None, None, None) => Some(CallSpec::Function(name)),
+        (None, Some(signature), None, None) => Some(CallSpec::Signature(signature)),
+        (None, None, Some(data), None) => Some(CallSpec::Raw(data)),
+        (None, None, None, Some(path)) => Some(CallSpec::Multicall(path)),
+        _ => anyhow::bail!("--function, --sig, --data and --multicall are mutually exclusive"),
+    };
+    let mut multicall_calls = None;
+    let mut decoded = None;
+
+    // Build transaction data; the mode is selected by which of --contract/--to/--function are set
+    let mode = (contract_path.as_deref(), to.as_deref(), call);
+    let (tx_to, tx_data, error_abi) = match mode {
+        (None, Some(to_str), None) => {
+            // ── Transfer mode: plain native-token transfer, empty data ──────────
+            println!("Mode: Transfer");
+            println!("To: {}", to_str);
+            println!("Value: {} wei", value);
+
+            if !args.is_empty() {
+                anyhow::bail!("Arguments are not allowed for a plain transfer");
+            }
+            if value.is_zero() {
+                println!("⚠ Transferring a zero value");
+            }
 
-    let abi: Abi = serde_json::from_value(abi_value)
-        .context("Failed to parse ABI")?;
+            let to_addr = H160::from_str(to_str)
+                .with_context(|| format!("Invalid recipient address: {}", to_str))?;
 
-    // Determine call mode vs. deploy mode
-    let is_call_mode = to.is_some() && function_name.is_some();
+            (Some(format!("{:?}", to_addr)), Vec::new(), None)
+        }
+        (Some(contract_path), Some(to_str), Some(CallSpec::Function(func_name))) => {
+            // ── Call mode: encode a function call ──────────────────────────────
+            println!("Mode: Function call");
+            println!("To: {}", to_str);
+
+            // Validate the 'to' address
+            let to_addr = H160::from_str(to_str)
+                .with_context(|| format!("Invalid contract address: {}", to_str))?;
+
+            // Look up the function in ABI
+            let (_, abi) = load_contract(contract_path, contract_name.as_deref())?;
+            let function = contract::find_function(&abi, func_name)?;
+            println!("Function: {}", contract::canonical_signature(function));
+            println!("Selector: 0x{}", hex::encode(function.short_signature()));
+
+            // Encode function call data
+            let call_data = encode_function_call(function, &args)?;
+            let artifact = metadata::artifact_source(contract_path)?;
+            decoded = Some(metadata::for_function(function, &call_data, Some(artifact))?);
+
+            (Some(format!("{:?}", to_addr)), call_data, Some(abi))
+        }
+        (None, Some(to_str), Some(CallSpec::Signature(signature))) => {
+            // ── Signature mode: encode a call from a human-readable signature ──
+            let function = abi_args::parse_signature(signature)?;
 
-    // Build transaction data
-    let (tx_to, tx_data) = if is_call_mode {
-        // ── Call mode: encode a function call ──────────────────────────────
-        let to_str = to.as_deref().unwrap();
-        let func_name = function_name.as_deref().unwrap();
+            println!("Mode: Function call (signature)");
+            println!("To: {}", to_str);
+            println!("Function: {}", function.signature());
+            println!("Selector: 0x{}", hex::encode(function.short_signature()));
 
-        println!("Mode: Function call");
-        println!("To: {}", to_str);
-        println!("Function: {}", func_name);
+            let to_addr = H160::from_str(to_str)
+                .with_context(|| format!("Invalid contract address: {}", to_str))?;
 
-        // Validate the 'to' address
-        let to_addr = H160::from_str(to_str)
-            .with_context(|| format!("Invalid contract address: {}", to_str))?;
+            let call_data = encode_function_call(&function, &args)?;
+            decoded = Some(metadata::for_function(&function, &call_data, None)?);
 
-        // Look up the function in ABI
-        let function = abi
-            .function(func_name)
-            .with_context(|| format!("Function '{}' not found in ABI", func_name))?;
+            (Some(format!("{:?}", to_addr)), call_data, None)
+        }
+        (None, Some(to_str), Some(CallSpec::Raw(data))) => {
+            // ── Raw calldata mode: data is used exactly as given ───────────────
+            println!("Mode: Raw calldata");
+            println!("To: {}", to_str);
 
-        // Encode function call data
-        let call_data = if let Some(args_str) = args {
-            let args_vec: Vec<&str> = args_str.split(',').map(|s| s.trim()).collect();
+            if !args.is_empty() {
+                anyhow::bail!("Arguments cannot be combined with --data; encode them into the calldata");
+            }
 
-            if args_vec.len() != function.inputs.len() {
-                anyhow::bail!(
-                    "Function '{}' expects {} argument(s) but {} were provided",
-                    func_name,
-                    function.inputs.len(),
-                    args_vec.len()
-                );
+            let to_addr = H160::from_str(to_str)
+                .with_context(|| format!("Invalid contract address: {}", to_str))?;
+            let call_data = hex::decode(data.trim().strip_prefix("0x").unwrap_or(data.trim()))
+                .context("Invalid --data: expected hex calldata")?;
+            if let Some(selector) = call_data.get(..4) {
+                println!("Selector: 0x{}", hex::encode(selector));
+            }
+
+            (Some(format!("{:?}", to_addr)), call_data, None)
+        }
+        (None, to_str, Some(CallSpec::Multicall(path))) => {
+            // ── Multicall mode: several calls batched into one Multicall3 call ──
+            if !args.is_empty() {
+                anyhow::bail!("Arguments cannot be combined with --multicall; give each call its own args");
             }
+            if !value.is_zero() {
+                anyhow::bail!("--value cannot be combined with --multicall; give each call its own value");
+            }
+
+            let to_str = to_str.unwrap_or(DEFAULT_MULTICALL3);
+            let to_addr = H160::from_str(to_str)
+                .with_context(|| format!("Invalid Multicall3 address: {}", to_str))?;
+            let calls = load_multicall(path)?;
+            let call_data = multicall::encode(&calls)?;
+
+            println!("Mode: Multicall3 ({})", multicall::aggregate_function(&calls).name);
+            println!("To: {:?}", to_addr);
+            multicall::print_calls(&calls);
+            println!("⚠ The calls are made by Multicall3, not by {}; calls restricted to the sender will revert", from);
+
+            multicall_calls = Some(calls);
+            (Some(format!("{:?}", to_addr)), call_data, None)
+        }
+        (Some(contract_path), None, None) => {
+            // ── Deploy mode: bytecode + encoded constructor args ───────────────
+            println!("Mode: Contract deployment");
 
-            let tokens: Vec<Token> = args_vec
+            let (artifact, abi) = load_contract(contract_path, contract_name.as_deref())?;
+
+            // Fill in library addresses before the bytecode can be decoded
+            let links = links
                 .iter()
-                .zip(function.inputs.iter())
-                .map(|(arg, param)| parse_arg_to_token(arg, &param.kind))
-                .collect::<Result<Vec<_>>>()
-                .context("Failed to parse function arguments")?;
-
-            function
-                .encode_input(&tokens)
-                .context("Failed to encode function call")?
+                .map(|spec| LibraryLink::from_str(spec))
+                .collect::<Result<Vec<_>>>()?;
+            let bytecode = contract::link_bytecode(&artifact.bytecode, &artifact.link_references, &links)?;
+
+            let bytecode_bytes = hex::decode(&bytecode)
+                .context("Failed to decode bytecode hex")?;
+
+            let (constructor_data, tokens) = match abi.constructor() {
+                Some(constructor) => {
+                    let tokens = abi_args::tokenize_args("Constructor", &args, &constructor.inputs)?;
+                    let data = constructor
+                        .encode_input(bytecode_bytes, &tokens)
+                        .context("Failed to encode constructor")?;
+                    (data, tokens)
+                }
+                None if !args.is_empty() => {
+                    anyhow::bail!("Contract has no constructor but arguments were provided");
+                }
+                None => (bytecode_bytes, Vec::new()),
+            };
+            let artifact = metadata::artifact_source(contract_path)?;
+            decoded = Some(metadata::for_constructor(abi.constructor(), &tokens, Some(artifact))?);
+
+            (None, constructor_data, Some(abi))
+        }
+        (Some(_), Some(_), None) => anyhow::bail!(
+            "--to with --contract requires --function (omit --contract for a plain transfer)"
+        ),
+        (Some(_), _, Some(CallSpec::Multicall(_))) => {
+            anyhow::bail!("--multicall does not use --contract; give each call its own contract")
+        }
+        (Some(_), Some(_), Some(_)) => {
+            anyhow::bail!("--sig and --data do not use --contract; remove one or the other")
+        }
+        (_, None, Some(_)) => anyhow::bail!("--function, --sig and --data require --to"),
+        (None, Some(_), Some(CallSpec::Function(_))) => {
+            anyhow::bail!("--function requires --contract (or use --sig without an artifact)")
+        }
+        (None, None, None) => anyhow::bail!(
+            "Specify --contract to deploy or call a contract, or --to and --value for a plain transfer"
+        ),
+    };
+
+    // A batch sends the sum of the values forwarded to its calls
+    let value = match &multicall_calls {
+        Some(calls) => multicall::total_value(calls)?,
+        None => value,
+    };
+
+    if !links.is_empty() && tx_to.is_some() {
+        anyhow::bail!("--link only applies to contract deployments");
+    }
+
+    // CREATE2: the factory is called with salt ‖ init code and deploys the contract
+    let create2 = if use_create2 {
+        if tx_to.is_some() {
+            anyhow::bail!("--create2 only applies to contract deployments");
+        }
+        let salt = salt.as_deref().context("--create2 requires --salt")?;
+        let salt = H256::from_str(salt.trim())
+            .with_context(|| format!("Invalid --salt: expected 32 bytes of hex, got {}", salt))?;
+        let factory = factory.as_deref().unwrap_or(DEFAULT_CREATE2_FACTORY);
+        let factory = H160::from_str(factory)
+            .with_context(|| format!("Invalid --factory address: {}", factory))?;
+        let address = contract::create2_address(factory, salt, &tx_data);
+        println!("CREATE2 factory: {:?}", factory);
+        println!("CREATE2 salt: {:?}", salt);
+        println!("Predicted contract address: {:?}", address);
+        Some((factory, salt, address))
+    } else {
+        None
+    };
+    let deploys = tx_to.is_none();
+    let (tx_to, tx_data) = match create2 {
+        Some((factory, salt, _)) => (
+            Some(format!("{:?}", factory)),
+            [salt.as_bytes(), &tx_data[..]].concat(),
+        ),
+        None => (tx_to, tx_data),
+    };
+
+    // Parse from address
+    let from_addr = H160::from_str(&from)
+        .context("Invalid from address")?;
+
+    // Blob sidecar: KZG commitments and proofs are computed locally, without the RPC
+    let sidecar = if blob_files.is_empty() {
+        None
+    } else {
+        if tx_to.is_none() {
+            anyhow::bail!("Blob transactions cannot deploy contracts; --blob requires --to");
+        }
+        println!("Computing KZG commitments and proofs...");
+        Some(blob::sidecar_from_files(&blob_files, blob_encoding, blob_proofs)?)
+    };
+    let blob_hashes = sidecar.as_ref().map(blob::versioned_hashes).unwrap_or_default();
+    for (i, hash) in blob_hashes.iter().enumerate() {
+        println!("Blob {} versioned hash: {:?}", i, hash);
+    }
+    let max_blob_fee = max_blob_fee.as_deref().map(units::parse_gwei).transpose()
+        .context("Invalid --max-blob-fee")?;
+
+    // EIP-7702: delegate the sender's code, signed by the same key as the transaction
+    let delegate = delegate
+        .as_deref()
+        .map(|d| H160::from_str(d).with_context(|| format!("Invalid --delegate address: {}", d)))
+        .transpose()?;
+    if let Some(delegate) = delegate {
+        if tx_to.is_none() {
+            anyhow::bail!("Set-code transactions cannot deploy contracts; --delegate requires --to");
+        }
+        if sidecar.is_some() {
+            anyhow::bail!("--delegate cannot be combined with --blob");
+        }
+        if delegate.is_zero() {
+            println!("Delegation: clear the current delegation of {}", from);
         } else {
-            if !function.inputs.is_empty() {
-                let param_list: Vec<String> = function
-                    .inputs
-                    .iter()
-                    .map(|p| format!("{}: {}", p.name, p.kind))
-                    .collect();
-                anyhow::bail!(
-                    "Function '{}' requires {} parameter(s) but none were provided: {}\n\
-                     Use --args to supply function arguments.",
-                    func_name,
-                    function.inputs.len(),
-                    param_list.join(", ")
-                );
-            }
-            function
-                .encode_input(&[])
-                .context("Failed to encode function call")?
-        };
+            println!("Delegation: {} -> {:?}", from, delegate);
+        }
+    }
 
-        (Some(format!("{:?}", to_addr)), call_data)
+    let overrides = fees::parse_overrides(
+        max_fee.as_deref(),
+        priority_fee.as_deref(),
+        gas_price.as_deref(),
+    )?;
+
+    let (chain_id, nonce, fees, max_fee_per_blob_gas, gas_limit, gas_limit_source, access_list) = if offline {
+        // ── Offline: everything comes from the user, the RPC is never contacted ──
+        println!("Offline mode: no RPC requests will be made");
+        let chain_id = chain_id.context("Offline mode requires --chain-id")?;
+        let nonce = nonce.context("Offline mode requires --nonce")?;
+        let gas_limit = gas_limit.context("Offline mode requires --gas-limit")?;
+        let fees = fees::explicit_fees(&overrides)?;
+        let max_fee_per_blob_gas = match sidecar {
+            Some(_) => Some(max_blob_fee.context("Offline blob transactions require --max-blob-fee")?),
+            None => None,
+        };
+        println!("Chain ID: {}", chain_id);
+        if create2.is_some() {
+            println!("⚠ Offline: the CREATE2 factory and the predicted address are not checked");
+        }
+        if let Some(url) = &rpc_url {
+            println!("RPC URL recorded for broadcast: {}", url);
+        }
+        (chain_id, nonce, fees, max_fee_per_blob_gas, gas_limit, GasLimitSource::User, None)
     } else {
-        // ── Deploy mode: bytecode + encoded constructor args ───────────────
-        println!("Mode: Contract deployment");
+        if chain_id.is_some() {
+            anyhow::bail!("--chain-id can only be used with --offline");
+        }
+        let rpc_url = rpc_url
+            .as_deref()
+            .context("An RPC URL is required unless --offline is used")?;
+
+        // Connect to RPC provider
+        println!("Connecting to RPC: {}", rpc_url);
+        let provider = Provider::<Http>::try_from(rpc_url)
+            .context("Failed to create provider")?;
+
+        // Fetch chain ID from RPC
+        println!("Fetching chain ID from RPC...");
+        let chain_id = provider
+            .get_chainid()
+            .await
+            .context("Failed to fetch chain ID from RPC")?
+            .as_u64();
+        println!("Chain ID: {}", chain_id);
+
+        // Fetch nonce, unless the caller assigned one
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => {
+                println!("Fetching nonce for address: {}", from);
+                provider
+                    .get_transaction_count(from_addr, None)
+                    .await
+                    .context("Failed to fetch nonce")?
+                    .as_u64()
+            }
+        };
 
-        let constructor_data = if let Some(args_str) = args {
-            if let Some(constructor) = abi.constructor() {
-                let args_vec: Vec<&str> = args_str.split(',').map(|s| s.trim()).collect();
+        // Resolve fees (explicit overrides, then the fee strategy)
+        println!("Fetching gas price information (strategy: {:?})...", fee_strategy);
+        let fees = fees::resolve_fees(&provider, fee_strategy, &overrides).await?;
+        let max_fee_per_blob_gas = match sidecar {
+            Some(_) => Some(fees::resolve_blob_fee(&provider, max_blob_fee).await?),
+            None => None,
+        };
 
-                if args_vec.len() != constructor.inputs.len() {
+        let to_addr = tx_to
+            .as_deref()
+            .map(H160::from_str)
+            .transpose()
+            .context("Invalid to address")?;
+        // The delegate must be deployed, or the account would run empty code
+        if let Some(delegate) = delegate.filter(|d| !d.is_zero()) {
+            let code = provider
+                .get_code(delegate, None)
+                .await
+                .context("Failed to fetch the delegate's code")?;
+            if code.is_empty() {
+                if !force {
                     anyhow::bail!(
-                        "Constructor expects {} argument(s) but {} were provided",
-                        constructor.inputs.len(),
-                        args_vec.len()
+                        "Delegate {:?} has no code on chain {}; pass --force to delegate anyway",
+                        delegate,
+                        chain_id
                     );
                 }
+                println!("⚠ Delegate has no code; continuing because --force was given");
+            } else if code.starts_with(&DELEGATION_DESIGNATOR_PREFIX) {
+                println!("⚠ Delegate is itself a delegated account; delegations are not followed, so the account would run no code");
+            } else {
+                println!("Delegate code: {} bytes", code.len());
+            }
+        }
+
+        // The factory must exist, and the target address must still be free, or the
+        // transaction would deploy nothing
+        if let Some((factory, _, address)) = create2 {
+            let factory_code = provider
+                .get_code(factory, None)
+                .await
+                .context("Failed to fetch the CREATE2 factory's code")?;
+            if factory_code.is_empty() {
+                anyhow::bail!("CREATE2 factory {:?} is not deployed on chain {}", factory, chain_id);
+            }
+            let existing = provider
+                .get_code(address, None)
+                .await
+                .context("Failed to fetch code at the predicted contract address")?;
+            if !existing.is_empty() {
+                anyhow::bail!(
+                    "A contract is already deployed at {:?} on chain {}; use a different --salt",
+                    address,
+                    chain_id
+                );
+            }
+            println!("Predicted address is free");
+        }
+
+        let mut call = CallRequest {
+            from: from_addr,
+            to: to_addr,
+            data: &tx_data,
+            value,
+            access_list: None,
+            blob_versioned_hashes: &blob_hashes,
+            max_fee_per_blob_gas,
+            delegation: delegate.map(|d| (from_addr, d)),
+        };
 
-                let tokens: Vec<Token> = args_vec
-                    .iter()
-                    .zip(constructor.inputs.iter())
-                    .map(|(arg, param)| parse_arg_to_token(arg, &param.kind))
-                    .collect::<Result<Vec<_>>>()
-                    .context("Failed to parse constructor arguments")?;
-
-                let bytecode_bytes = hex::decode(&bytecode)
-                    .context("Failed to decode bytecode hex")?;
-                constructor
-                    .encode_input(bytecode_bytes, &tokens)
-                    .context("Failed to encode constructor")?
+        // Simulate against the pending block so a reverting transaction is caught
+        // before it is carried to the signer
+        println!("Simulating transaction (eth_call on the pending block)...");
+        let reverted = match simulate::simulate(&provider, &call, error_abi.as_ref()).await? {
+            Simulation::Success => {
+                println!("Simulation succeeded");
+                false
+            }
+            // A reverting transaction cannot be estimated either, so its gas limit must be given
+            Simulation::Reverted(reason) if force && gas_limit.is_none() => anyhow::bail!(
+                "Transaction would fail: {}\neth_estimateGas cannot estimate a reverting transaction; \
+                 pass --gas-limit together with --force",
+                reason
+            ),
+            Simulation::Reverted(reason) if force => {
+                println!("⚠ Simulation failed: {}", reason);
+                println!("⚠ Continuing because --force was given");
+                true
+            }
+            Simulation::Reverted(reason) => anyhow::bail!(
+                "Transaction would fail: {}\nNo file was written; pass --force (with --gas-limit) to prepare it anyway",
+                reason
+            ),
+        };
+
+        // Optional EIP-2930 access list; it is traced from a successful execution
+        let access_list = if use_access_list && reverted {
+            println!("⚠ Skipping the access list, which cannot be generated for a reverting transaction");
+            None
+        } else if use_access_list {
+            println!("Generating access list (eth_createAccessList)...");
+            let list = gas::create_access_list(&provider, &call).await?;
+            if list.0.is_empty() {
+                println!("Access list is empty, sending without one");
+                None
             } else {
-                anyhow::bail!("Contract has no constructor but arguments were provided");
+                Some(list)
             }
         } else {
-            // Validate that the constructor does not require parameters
-            if let Some(constructor) = abi.constructor() {
-                if !constructor.inputs.is_empty() {
-                    let param_list: Vec<String> = constructor
-                        .inputs
-                        .iter()
-                        .map(|p| format!("{}: {}", p.name, p.kind))
-                        .collect();
-                    anyhow::bail!(
-                        "Constructor requires {} parameter(s) but none were provided: {}\n\
-                         Use --args to supply constructor arguments.",
-                        constructor.inputs.len(),
-                        param_list.join(", ")
-                    );
-                }
+            None
+        };
+
+        // Gas limit: user-supplied, or estimated with a safety multiplier
+        let (gas_limit, gas_limit_source) = match gas_limit {
+            Some(limit) => (limit, GasLimitSource::User),
+            None => {
+                println!("Estimating gas...");
+                call.access_list = access_list.as_ref();
+                let limit = gas::estimate_gas_limit(&provider, &call, gas_multiplier).await?;
+                (limit, GasLimitSource::Estimated)
             }
-            hex::decode(&bytecode).context("Failed to decode bytecode")?
         };
 
-        (None, constructor_data)
+        (chain_id, nonce, fees, max_fee_per_blob_gas, gas_limit, gas_limit_source, access_list)
     };
 
-    // Connect to RPC provider
-    println!("Connecting to RPC: {}", rpc_url);
-    let provider = Provider::<Http>::try_from(&rpc_url)
-        .context("Failed to create provider")?;
-
-    // Parse from address
-    let from_addr = H160::from_str(&from)
-        .context("Invalid from address")?;
+    if sidecar.is_some() && matches!(fees, Fees::Legacy { .. }) {
+        anyhow::bail!("Blob transactions require EIP-1559 fees, but only a legacy gas price is available");
+    }
+    if delegate.is_some() && matches!(fees, Fees::Legacy { .. }) {
+        anyhow::bail!("Set-code transactions require EIP-1559 fees, but only a legacy gas price is available");
+    }
 
-    // Fetch chain ID from RPC
-    println!("Fetching chain ID from RPC...");
-    let chain_id = provider
-        .get_chainid()
-        .await
-        .context("Failed to fetch chain ID from RPC")?
-        .as_u64();
-    println!("Chain ID: {}", chain_id);
-
-    // Fetch nonce
-    println!("Fetching nonce for address: {}", from);
-    let nonce = provider
-        .get_transaction_count(from_addr, None)
-        .await
-        .context("Failed to fetch nonce")?;
-
-    // Fetch fee data (EIP-1559 or legacy)
-    println!("Fetching gas price information...");
-    let fee_data = provider
-        .fee_history(1, ethers::types::BlockNumber::Latest, &[])
-        .await;
-
-    let (max_fee_per_gas, max_priority_fee_per_gas, gas_price) =
-        if let Ok(fee_history) = fee_data {
-            // EIP-1559
-            let base_fee = fee_history
-                .base_fee_per_gas
-                .first()
-                .copied()
-                .unwrap_or(U256::from(1_000_000_000u64)); // 1 gwei default
-
-            let priority_fee = U256::from(1_500_000_000u64); // 1.5 gwei
-            let max_fee: U256 = base_fee * 2 + priority_fee;
-
-            (Some(max_fee.as_u64()), Some(priority_fee.as_u64()), None)
-        } else {
-            // Legacy gas price
-            let gas_price = provider
-                .get_gas_price()
-                .await
-                .context("Failed to fetch gas price")?;
-            (None, None, Some(gas_price.as_u64()))
-        };
+    // The sender's nonce is bumped before authorizations are processed, so a
+    // self-signed authorization must use the next nonce
+    let authorization_list = delegate.map(|d| {
+        vec![Authorization {
+            chain_id,
+            address: format!("{:?}", d),
+            nonce: nonce + 1,
+        }]
+    });
+
+    // The deployed address follows from the sender and nonce, or from the CREATE2 inputs
+    let contract_address = match create2 {
+        Some((_, _, address)) => Some(address),
+        None if deploys => Some(get_contract_address(from_addr, nonce)),
+        None => None,
+    };
 
-    // Gas limit
-    let estimated_gas = gas_limit.unwrap_or(3_000_000u64);
+    let (max_fee_per_gas, max_priority_fee_per_gas, gas_price) = match fees {
+        Fees::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        } => (Some(max_fee_per_gas), Some(max_priority_fee_per_gas), None),
+        Fees::Legacy { gas_price } => (None, None, Some(gas_price)),
+    };
 
     // Create unsigned transaction
     let unsigned_tx = UnsignedTransaction {
+        from: Some(format!("{:?}", from_addr)),
         to: tx_to,
         data: hex::encode(&tx_data),
-        nonce: nonce.as_u64(),
-        gas_limit: estimated_gas,
+        nonce,
+        gas_limit,
+        gas_limit_source: Some(gas_limit_source),
         gas_price,
         max_fee_per_gas,
         max_priority_fee_per_gas,
+        access_list,
+        max_fee_per_blob_gas,
+        blob_versioned_hashes: sidecar.as_ref().map(|_| blob_hashes.clone()),
+        blob_sidecar: sidecar,
+        authorization_list,
+        create2: create2.map(|(factory, salt, _)| Create2Deployment {
+            factory: format!("{:?}", factory),
+            salt,
+        }),
+        multicall: multicall_calls,
+        decoded,
+        contract_address: contract_address.map(|address| format!("{:?}", address)),
         chain_id,
         value,
-        rpc_url: rpc_url.clone(),
+        rpc_url,
     };
 
-    // Save to output file
-    println!("Saving unsigned transaction to: {}", output);
-    let json = serde_json::to_string_pretty(&unsigned_tx)
-        .context("Failed to serialize transaction")?;
+    Ok(unsigned_tx)
+}
 
-    fs::write(&output, json).context("Failed to write output file")?;
+pub async fn execute(params: PrepareParams) -> Result<()> {
+    run(params).await?;
+    Ok(())
+}
 
-    println!("\n✓ Unsigned transaction prepared successfully!");
+/// Print the nonce, gas and fee fields of a prepared transaction
+fn print_summary(unsigned_tx: &UnsignedTransaction) {
     println!("  Nonce: {}", unsigned_tx.nonce);
-    println!("  Gas limit: {}", unsigned_tx.gas_limit);
+    println!(
+        "  Gas limit: {} ({})",
+        unsigned_tx.gas_limit,
+        match unsigned_tx.gas_limit_source {
+            Some(GasLimitSource::Estimated) => "estimated",
+            Some(GasLimitSource::Forge) => "from forge script",
+            _ => "user-supplied",
+        }
+    );
     if let Some(gp) = unsigned_tx.gas_price {
-        println!("  Gas price: {} gwei", gp / 1_000_000_000);
+        println!("  Gas price: {} gwei", units::format_gwei(gp));
     } else {
         println!(
             "  Max fee per gas: {} gwei",
-            unsigned_tx.max_fee_per_gas.unwrap() / 1_000_000_000
+            units::format_gwei(unsigned_tx.max_fee_per_gas.unwrap())
         );
         println!(
             "  Max priority fee per gas: {} gwei",
-            unsigned_tx.max_priority_fee_per_gas.unwrap() / 1_000_000_000
+            units::format_gwei(unsigned_tx.max_priority_fee_per_gas.unwrap())
         );
     }
+    if let (Some(sidecar), Some(blob_fee)) = (&unsigned_tx.blob_sidecar, unsigned_tx.max_fee_per_blob_gas) {
+        println!(
+            "  Blobs: {} (max fee per blob gas: {} gwei)",
+            sidecar.blobs.len(),
+            units::format_gwei(blob_fee)
+        );
+    }
+    for authorization in unsigned_tx.authorization_list.iter().flatten() {
+        println!(
+            "  Delegation: {} (authorization nonce {})",
+            authorization.address, authorization.nonce
+        );
+    }
+    if let Some(address) = &unsigned_tx.contract_address {
+        println!("  Contract address: {}", address);
+    }
+    if let Some(list) = &unsigned_tx.access_list {
+        println!(
+            "  Access list: {} address(es) ({})",
+            list.0.len(),
+            if unsigned_tx.gas_price.is_some() { "EIP-2930 transaction" } else { "EIP-1559 transaction" }
+        );
+    }
+}
 
-    let message = "Unsigned transaction prepared successfully!".to_string();
+/// Where the calldata of a contract call comes from
+enum CallSpec<'a> {
+    /// Function name looked up in the artifact ABI
+    Function(&'a str),
+    /// Human-readable signature such as `transfer(address,uint256)`
+    Signature(&'a str),
+    /// Pre-encoded calldata
+    Raw(&'a str),
+    /// File listing calls to batch through Multicall3
+    Multicall(&'a str),
+}
 
-    Ok(PrepareResult {
-        unsigned_tx,
-        success: true,
-        message,
-    })
+/// Calls file for `--multicall`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MulticallSpec {
+    calls: Vec<MulticallEntry>,
 }
 
-pub async fn execute(
-    contract_path: String,
-    rpc_url: String,
-    from: String,
-    to: Option<String>,
-    function_name: Option<String>,
-    args: Option<String>,
-    value: String,
-    output: String,
-    gas_limit: Option<u64>,
-) -> Result<()> {
-    let params = PrepareParams {
-        contract: contract_path,
-        rpc_url,
-        from,
-        to,
-        function_name,
-        args,
-        value,
-        output,
-        gas_limit,
-    };
+/// One call of a `--multicall` file; the calldata comes from the same options as a
+/// single call
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MulticallEntry {
+    to: String,
+    #[serde(default)]
+    contract: Option<String>,
+    #[serde(default)]
+    contract_name: Option<String>,
+    #[serde(default)]
+    function: Option<String>,
+    #[serde(default)]
+    sig: Option<String>,
+    #[serde(default)]
+    data: Option<String>,
+    #[serde(default)]
+    args: Vec<Value>,
+    #[serde(default)]
+    value: Option<String>,
+    #[serde(default)]
+    allow_failure: bool,
+}
 
-    run(params).await?;
-    Ok(())
+/// Read a `--multicall` file and encode each of its calls
+fn load_multicall(path: &str) -> Result<Vec<MulticallCall>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read multicall file: {}", path))?;
+    let spec: MulticallSpec = serde_json::from_str(&content)
+        .context("Failed to parse multicall file (expected {\"calls\": [...]})")?;
+    if spec.calls.is_empty() {
+        anyhow::bail!("Multicall file contains no calls");
+    }
+
+    spec.calls
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let target = H160::from_str(&entry.to)
+                .with_context(|| format!("Invalid target address in call {}: {}", i + 1, entry.to))?;
+            let value = entry
+                .value
+                .as_deref()
+                .map(units::parse_wei)
+                .transpose()
+                .with_context(|| format!("Invalid value in call {}", i + 1))?
+                .unwrap_or_default();
+
+            let (call_data, function) = match (&entry.contract, &entry.function, &entry.sig, &entry.data) {
+                (Some(contract_path), Some(name), None, None) => {
+                    let (_, abi) = load_contract(contract_path, entry.contract_name.as_deref())?;
+                    let function = contract::find_function(&abi, name)?;
+                    (encode_function_call(function, &entry.args)?, Some(contract::canonical_signature(function)))
+                }
+                (None, None, Some(signature), None) => {
+                    let function = abi_args::parse_signature(signature)?;
+                    (encode_function_call(&function, &entry.args)?, Some(contract::canonical_signature(&function)))
+                }
+                (None, None, None, Some(data)) if entry.args.is_empty() => {
+                    let call_data = hex::decode(data.trim().strip_prefix("0x").unwrap_or(data.trim()))
+                        .with_context(|| format!("Invalid data in call {}: expected hex calldata", i + 1))?;
+                    (call_data, None)
+                }
+                (None, None, None, None) if entry.args.is_empty() => (Vec::new(), None),
+                _ => anyhow::bail!(
+                    "Call {} must use one of: contract with function, sig, or data (args only with contract/function or sig)",
+                    i + 1
+                ),
+            };
+
+            Ok(MulticallCall {
+                target: format!("{:?}", target),
+                value,
+                allow_failure: entry.allow_failure,
+                call_data: hex::encode(call_data),
+                function,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("Invalid multicall file: {}", path))
 }
 
-fn parse_arg_to_token(arg: &str, param_type: &ethers::abi::ParamType) -> Result<Token> {
-    use ethers::abi::ParamType;
+/// ABI-encode a function call (selector + arguments)
+fn encode_function_call(function: &Function, args: &[Value]) -> Result<Vec<u8>> {
+    let target = format!("Function '{}'", function.name);
+    let tokens = abi_args::tokenize_args(&target, args, &function.inputs)?;
+    function
+        .encode_input(&tokens)
+        .context("Failed to encode function call")
+}
 
-    match param_type {
-        ParamType::Address => {
-            let addr = H160::from_str(arg)?;
-            Ok(Token::Address(addr))
-        }
-        ParamType::Uint(_) => {
-            let value = U256::from_dec_str(arg)?;
-            Ok(Token::Uint(value))
-        }
-        ParamType::Int(_) => {
-            let value = U256::from_dec_str(arg)?;
-            Ok(Token::Int(value))
-        }
-        ParamType::Bool => {
-            let value = arg.parse::<bool>()?;
-            Ok(Token::Bool(value))
-        }
-        ParamType::String => Ok(Token::String(arg.to_string())),
-        ParamType::Bytes => {
-            let bytes = hex::decode(arg.strip_prefix("0x").unwrap_or(arg))?;
-            Ok(Token::Bytes(bytes))
-        }
-        _ => anyhow::bail!("Unsupported parameter type: {:?}", param_type),
+/// Load the artifact and its parsed ABI from a compiled contract JSON
+fn load_contract(path: &str, contract_name: Option<&str>) -> Result<(ContractArtifact, Abi)> {
+    let artifact = contract::parse_contract_json(path, contract_name)
+        .context("Failed to parse contract JSON")?;
+    if let Some(name) = &artifact.name {
+        println!("Contract name: {}", name);
     }
+    let abi: Abi = serde_json::from_value(artifact.abi.clone())
+        .context("Failed to parse ABI")?;
+    Ok((artifact, abi))
 }
@@ -0,0 +1,270 @@
+use anyhow::{Context, Result};
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::H160,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::str::FromStr;
+
+use super::prepare::{self, PrepareParams};
+use crate::types::prepare_output::UnsignedBundle;
+use crate::utils::fees::{self, FeeStrategy, Fees};
+use crate::utils::units;
+
+/// Options for `prepare --bundle`
+#[derive(Debug, Clone)]
+pub struct BundleParams {
+    /// Bundle spec: a JSON file listing the transactions in order
+    pub path: String,
+    /// RPC endpoint; optional in offline mode, where it is only recorded for broadcast
+    pub rpc_url: Option<String>,
+    pub from: String,
+    pub output: String,
+    pub gas_multiplier: f64,
+    pub fee_strategy: FeeStrategy,
+    pub max_fee: Option<String>,
+    pub priority_fee: Option<String>,
+    pub gas_price: Option<String>,
+    pub offline: bool,
+    pub chain_id: Option<u64>,
+    /// Nonce of the first transaction (offline mode only)
+    pub nonce: Option<u64>,
+    /// Write the bundle even if a transaction's simulation reverts
+    pub force: bool,
+    pub access_list: bool,
+}
+
+/// Bundle spec file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BundleSpec {
+    transactions: Vec<BundleEntry>,
+}
+
+/// One transaction of a bundle spec; the fields mirror the `prepare` flags
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BundleEntry {
+    #[serde(default)]
+    contract: Option<String>,
+    #[serde(default)]
+    contract_name: Option<String>,
+    #[serde(default)]
+    to: Option<String>,
+    #[serde(default)]
+    function: Option<String>,
+    #[serde(default)]
+    sig: Option<String>,
+    #[serde(default)]
+    data: Option<String>,
+    /// JSON values, or Solidity literals as strings
+    #[serde(default)]
+    args: Vec<Value>,
+    #[serde(default = "default_value")]
+    value: String,
+    #[serde(default)]
+    gas_limit: Option<u64>,
+    /// Write the transaction even if its simulation reverts
+    #[serde(default)]
+    force: bool,
+    #[serde(default)]
+    create2: bool,
+    #[serde(default)]
+    salt: Option<String>,
+    #[serde(default)]
+    factory: Option<String>,
+    #[serde(default)]
+    links: Vec<String>,
+}
+
+fn default_value() -> String {
+    "0".to_string()
+}
+
+pub async fn execute(params: BundleParams) -> Result<()> {
+    println!("Preparing unsigned transaction bundle from: {}", params.path);
+
+    let content = fs::read_to_string(&params.path)
+        .with_context(|| format!("Failed to read bundle file: {}", params.path))?;
+    let spec: BundleSpec = serde_json::from_str(&content)
+        .context("Failed to parse bundle file (expected {\"transactions\": [...]})")?;
+    if spec.transactions.is_empty() {
+        anyhow::bail!("Bundle file contains no transactions");
+    }
+
+    let from_addr = H160::from_str(&params.from).context("Invalid from address")?;
+    println!("From: {:?}", from_addr);
+    println!("Transactions: {}", spec.transactions.len());
+
+    let overrides = fees::parse_overrides(
+        params.max_fee.as_deref(),
+        params.priority_fee.as_deref(),
+        params.gas_price.as_deref(),
+    )?;
+
+    // Chain, starting nonce and one fee setting are shared by the whole bundle
+    let (chain_id, first_nonce, fees) = if params.offline {
+        println!("Offline mode: no RPC requests will be made");
+        let chain_id = params.chain_id.context("Offline mode requires --chain-id")?;
+        let nonce = params.nonce.context("Offline mode requires --nonce")?;
+        (chain_id, nonce, fees::explicit_fees(&overrides)?)
+    } else {
+        if params.chain_id.is_some() || params.nonce.is_some() {
+            anyhow::bail!("--chain-id and --nonce can only be used with --offline");
+        }
+        let rpc_url = params
+            .rpc_url
+            .as_deref()
+            .context("An RPC URL is required unless --offline is used")?;
+        println!("Connecting to RPC: {}", rpc_url);
+        let provider = Provider::<Http>::try_from(rpc_url)
+            .context("Failed to create provider")?;
+
+        println!("Fetching chain ID from RPC...");
+        let chain_id = provider
+            .get_chainid()
+            .await
+            .context("Failed to fetch chain ID from RPC")?
+            .as_u64();
+
+        println!("Fetching nonce for address: {:?}", from_addr);
+        let nonce = provider
+            .get_transaction_count(from_addr, None)
+            .await
+            .context("Failed to fetch nonce")?
+            .as_u64();
+
+        println!("Fetching gas price information (strategy: {:?})...", params.fee_strategy);
+        let fees = fees::resolve_fees(&provider, params.fee_strategy, &overrides).await?;
+        (chain_id, nonce, fees)
+    };
+    println!("Chain ID: {}", chain_id);
+    println!("First nonce: {}", first_nonce);
+
+    // Pass the resolved fees to every transaction as explicit values, in wei
+    let (max_fee, priority_fee, gas_price) = match fees {
+        Fees::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        } => (
+            Some(format!("{}wei", max_fee_per_gas)),
+            Some(format!("{}wei", max_priority_fee_per_gas)),
+            None,
+        ),
+        Fees::Legacy { gas_price } => (None, None, Some(format!("{}wei", gas_price))),
+    };
+
+    let count = spec.transactions.len();
+    let mut transactions = Vec::with_capacity(count);
+    // Contracts deployed by earlier transactions, which do not exist on chain yet
+    let mut deployed: Vec<(H160, usize)> = Vec::new();
+    for (i, entry) in spec.transactions.into_iter().enumerate() {
+        println!("\n── Transaction {}/{} (nonce {}) ──", i + 1, count, first_nonce + i as u64);
+
+        // A call to a contract deployed earlier in the bundle runs against empty code
+        // when simulated, so the estimate would be far too low
+        let target = entry
+            .to
+            .as_deref()
+            .map(H160::from_str)
+            .transpose()
+            .with_context(|| format!("Invalid 'to' address in transaction {}", i + 1))?;
+        if let Some((_, j)) = deployed.iter().find(|(address, _)| Some(*address) == target) {
+            if entry.gas_limit.is_none() && !params.offline {
+                anyhow::bail!(
+                    "Transaction {} calls the contract deployed by transaction {}, which cannot be simulated before it is deployed; set \"gas_limit\" for it",
+                    i + 1,
+                    j + 1
+                );
+            }
+            println!("Calls the contract deployed by transaction {}; simulation runs against empty code", j + 1);
+        }
+
+        let unsigned_tx = prepare::build(PrepareParams {
+            contract: entry.contract,
+            contract_name: entry.contract_name,
+            rpc_url: params.rpc_url.clone(),
+            from: params.from.clone(),
+            to: entry.to,
+            function_name: entry.function,
+            sig: entry.sig,
+            data: entry.data,
+            args: entry.args,
+            value: entry.value,
+            output: params.output.clone(),
+            gas_limit: entry.gas_limit,
+            gas_multiplier: params.gas_multiplier,
+            fee_strategy: params.fee_strategy,
+            max_fee: max_fee.clone(),
+            priority_fee: priority_fee.clone(),
+            gas_price: gas_price.clone(),
+            offline: params.offline,
+            chain_id: params.offline.then_some(chain_id),
+            nonce: Some(first_nonce + i as u64),
+            force: params.force || entry.force,
+            access_list: params.access_list,
+            blobs: Vec::new(),
+            blob_encoding: Default::default(),
+            blob_proofs: Default::default(),
+            max_blob_fee: None,
+            delegate: None,
+            create2: entry.create2,
+            salt: entry.salt,
+            factory: entry.factory,
+            links: entry.links,
+            multicall: None,
+        })
+        .await
+        .with_context(|| format!("Failed to prepare transaction {}", i + 1))?;
+
+        if unsigned_tx.chain_id != chain_id {
+            anyhow::bail!(
+                "RPC reported chain {} while preparing transaction {}, but the bundle is for chain {}",
+                unsigned_tx.chain_id,
+                i + 1,
+                chain_id
+            );
+        }
+        if let Some(address) = &unsigned_tx.contract_address {
+            deployed.push((H160::from_str(address).context("Invalid contract address")?, i));
+        }
+        transactions.push(unsigned_tx);
+    }
+
+    let bundle = UnsignedBundle {
+        chain_id,
+        rpc_url: params.rpc_url.clone(),
+        transactions,
+    };
+
+    // Save to output file
+    println!("\nSaving unsigned transaction bundle to: {}", params.output);
+    let json = serde_json::to_string_pretty(&bundle)
+        .context("Failed to serialize transaction bundle")?;
+    fs::write(&params.output, json).context("Failed to write output file")?;
+
+    println!("\n✓ Unsigned transaction bundle prepared successfully!");
+    println!("  Transactions: {}", count);
+    println!("  Nonces: {} to {}", first_nonce, first_nonce + count as u64 - 1);
+    for (i, tx) in bundle.transactions.iter().enumerate() {
+        match (&tx.contract_address, &tx.to) {
+            (Some(address), _) => println!("  {}. Deploy at {} (gas limit {})", i + 1, address, tx.gas_limit),
+            (None, Some(to)) => println!("  {}. To {} (gas limit {})", i + 1, to, tx.gas_limit),
+            (None, None) => println!("  {}. Deploy (gas limit {})", i + 1, tx.gas_limit),
+        }
+    }
+    match fees {
+        Fees::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        } => {
+            println!("  Max fee per gas: {} gwei", units::format_gwei(max_fee_per_gas));
+            println!("  Max priority fee per gas: {} gwei", units::format_gwei(max_priority_fee_per_gas));
+        }
+        Fees::Legacy { gas_price } => println!("  Gas price: {} gwei", units::format_gwei(gas_price)),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use anyhow::{Context, Result};
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{Bytes, H160, H256, U256},
+    utils::get_contract_address,
+};
+use serde::Deserialize;
+use std::fs;
+use std::str::FromStr;
+
+use crate::types::prepare_output::{
+    Create2Deployment, GasLimitSource, UnsignedBundle, UnsignedTransaction,
+};
+use crate::utils::fees::{self, FeeStrategy, Fees};
+use crate::utils::{contract, units};
+
+/// Options for `prepare --from-forge`
+#[derive(Debug, Clone)]
+pub struct ForgeImportParams {
+    /// `broadcast/<script>/<chain>/run-latest.json` written by `forge script`
+    pub path: String,
+    pub rpc_url: String,
+    /// Expected sender; must match the file when given
+    pub from: Option<String>,
+    pub output: String,
+    pub fee_strategy: FeeStrategy,
+    pub max_fee: Option<String>,
+    pub priority_fee: Option<String>,
+    pub gas_price: Option<String>,
+}
+
+/// The parts of a forge broadcast file used here
+#[derive(Debug, Deserialize)]
+struct ForgeBroadcast {
+    transactions: Vec<ForgeTransaction>,
+    chain: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForgeTransaction {
+    /// `CREATE`, `CREATE2` or `CALL`
+    transaction_type: String,
+    contract_name: Option<String>,
+    contract_address: Option<H160>,
+    function: Option<String>,
+    transaction: ForgeTransactionRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForgeTransactionRequest {
+    from: H160,
+    to: Option<H160>,
+    gas: Option<U256>,
+    value: Option<U256>,
+    /// Older forge versions call the calldata `data`
+    #[serde(alias = "data")]
+    input: Option<Bytes>,
+    nonce: Option<U256>,
+}
+
+pub async fn execute(params: ForgeImportParams) -> Result<()> {
+    println!("Importing forge script broadcast: {}", params.path);
+
+    let content = fs::read_to_string(&params.path)
+        .with_context(|| format!("Failed to read forge broadcast file: {}", params.path))?;
+    let broadcast: ForgeBroadcast = serde_json::from_str(&content)
+        .context("Failed to parse forge broadcast file (expected broadcast/.../run-latest.json)")?;
+
+    let first = broadcast
+        .transactions
+        .first()
+        .context("Forge broadcast file contains no transactions")?;
+
+    // Everything is signed by one keystore, so there must be a single sender
+    let from_addr = first.transaction.from;
+    if let Some(i) = broadcast.transactions.iter().position(|tx| tx.transaction.from != from_addr) {
+        anyhow::bail!(
+            "Transaction {} is sent from {:?}, not {:?}; all transactions must have one sender",
+            i + 1,
+            broadcast.transactions[i].transaction.from,
+            from_addr
+        );
+    }
+    if let Some(from) = &params.from {
+        let expected = H160::from_str(from).context("Invalid from address")?;
+        if expected != from_addr {
+            anyhow::bail!("--from is {:?}, but the forge script sends from {:?}", expected, from_addr);
+        }
+    }
+    println!("From: {:?}", from_addr);
+    println!("Transactions: {}", broadcast.transactions.len());
+
+    // Connect to RPC provider
+    println!("Connecting to RPC: {}", params.rpc_url);
+    let provider = Provider::<Http>::try_from(params.rpc_url.as_str())
+        .context("Failed to create provider")?;
+
+    println!("Fetching chain ID from RPC...");
+    let chain_id = provider
+        .get_chainid()
+        .await
+        .context("Failed to fetch chain ID from RPC")?
+        .as_u64();
+    println!("Chain ID: {}", chain_id);
+    if let Some(chain) = broadcast.chain.filter(|&chain| chain != chain_id) {
+        anyhow::bail!("The forge script ran against chain {}, but the RPC is on chain {}", chain, chain_id);
+    }
+
+    // Addresses of contracts deployed by the script depend on the nonces it simulated
+    // with, so the sequence is only valid from the same starting nonce
+    println!("Fetching nonce for address: {:?}", from_addr);
+    let nonce = provider
+        .get_transaction_count(from_addr, None)
+        .await
+        .context("Failed to fetch nonce")?
+        .as_u64();
+    for (i, tx) in broadcast.transactions.iter().enumerate() {
+        if let Some(forge_nonce) = tx.transaction.nonce {
+            if forge_nonce != U256::from(nonce + i as u64) {
+                anyhow::bail!(
+                    "forge script used nonce {} for transaction {}, but the account's next nonce is {}; re-run forge script so its nonces and addresses are current",
+                    forge_nonce,
+                    i + 1,
+                    nonce + i as u64
+                );
+            }
+        }
+    }
+
+    // One fee setting for the whole sequence
+    let overrides = fees::parse_overrides(
+        params.max_fee.as_deref(),
+        params.priority_fee.as_deref(),
+        params.gas_price.as_deref(),
+    )?;
+    println!("Fetching gas price information (strategy: {:?})...", params.fee_strategy);
+    let fees = fees::resolve_fees(&provider, params.fee_strategy, &overrides).await?;
+    let (max_fee_per_gas, max_priority_fee_per_gas, gas_price) = match fees {
+        Fees::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        } => (Some(max_fee_per_gas), Some(max_priority_fee_per_gas), None),
+        Fees::Legacy { gas_price } => (None, None, Some(gas_price)),
+    };
+
+    // Later transactions usually depend on earlier ones, so they cannot be simulated or
+    // estimated on their own; forge has already done both for the whole sequence
+    println!("Using forge's gas limits; the sequence was simulated by forge script");
+
+    let mut transactions = Vec::with_capacity(broadcast.transactions.len());
+    for (i, forge_tx) in broadcast.transactions.iter().enumerate() {
+        let nonce = nonce + i as u64;
+        let request = &forge_tx.transaction;
+        let data = request.input.clone().unwrap_or_default();
+        let gas_limit = request
+            .gas
+            .with_context(|| format!("Transaction {} has no gas limit", i + 1))?;
+        if gas_limit > U256::from(u64::MAX) {
+            anyhow::bail!("Transaction {} has a gas limit that does not fit in 64 bits", i + 1);
+        }
+
+        let (create2, contract_address) = match forge_tx.transaction_type.as_str() {
+            "CALL" => {
+                request.to.with_context(|| format!("Call {} has no recipient", i + 1))?;
+                (None, None)
+            }
+            "CREATE" => (None, Some(get_contract_address(from_addr, nonce))),
+            "CREATE2" => {
+                let factory = request
+                    .to
+                    .with_context(|| format!("CREATE2 transaction {} has no factory", i + 1))?;
+                let (salt, init_code) = data
+                    .split_at_checked(32)
+                    .with_context(|| format!("CREATE2 transaction {} has no salt", i + 1))?;
+                let salt = H256::from_slice(salt);
+                let address = contract::create2_address(factory, salt, init_code);
+                let deployment = Create2Deployment {
+                    factory: format!("{:?}", factory),
+                    salt,
+                };
+                (Some(deployment), Some(address))
+            }
+            other => anyhow::bail!("Unsupported forge transaction type '{}' in transaction {}", other, i + 1),
+        };
+        if let (Some(predicted), Some(forge_address)) = (contract_address, forge_tx.contract_address) {
+            if predicted != forge_address {
+                anyhow::bail!(
+                    "Transaction {} deploys to {:?}, but forge expected {:?}",
+                    i + 1,
+                    predicted,
+                    forge_address
+                );
+            }
+        }
+
+        let name = forge_tx.contract_name.as_deref().unwrap_or("?");
+        match (&contract_address, &forge_tx.function) {
+            (Some(address), _) => println!("  {}. Deploy {} at {:?} (nonce {})", i + 1, name, address, nonce),
+            (None, Some(function)) => println!("  {}. Call {}.{} (nonce {})", i + 1, name, function, nonce),
+            (None, None) => println!("  {}. Call {:?} (nonce {})", i + 1, request.to.unwrap_or_default(), nonce),
+        }
+
+        transactions.push(UnsignedTransaction {
+            from: Some(format!("{:?}", from_addr)),
+            to: request.to.map(|to| format!("{:?}", to)),
+            data: hex::encode(&data),
+            nonce,
+            gas_limit: gas_limit.as_u64(),
+            gas_limit_source: Some(GasLimitSource::Forge),
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            access_list: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            blob_sidecar: None,
+            authorization_list: None,
+            create2,
+            multicall: None,
+            decoded: None,
+            contract_address: contract_address.map(|address| format!("{:?}", address)),
+            chain_id,
+            value: request.value.unwrap_or_default(),
+            rpc_url: Some(params.rpc_url.clone()),
+        });
+    }
+
+    let bundle = UnsignedBundle {
+        chain_id,
+        rpc_url: Some(params.rpc_url.clone()),
+        transactions,
+    };
+
+    // Save to output file
+    println!("Saving unsigned transaction bundle to: {}", params.output);
+    let json = serde_json::to_string_pretty(&bundle)
+        .context("Failed to serialize transaction bundle")?;
+    fs::write(&params.output, json).context("Failed to write output file")?;
+
+    println!("\n✓ Unsigned transaction bundle prepared successfully!");
+    println!("  Transactions: {}", bundle.transactions.len());
+    println!(
+        "  Nonces: {} to {}",
+        nonce,
+        nonce + bundle.transactions.len() as u64 - 1
+    );
+    if let Some(gp) = gas_price {
+        println!("  Gas price: {} gwei", units::format_gwei(gp));
+    } else if let (Some(max_fee), Some(priority_fee)) = (max_fee_per_gas, max_priority_fee_per_gas) {
+        println!("  Max fee per gas: {} gwei", units::format_gwei(max_fee));
+        println!("  Max priority fee per gas: {} gwei", units::format_gwei(priority_fee));
+    }
+
+    Ok(())
+}
@@ -7,13 +7,15 @@ use axum::{
     Router,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::net::TcpListener;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use super::prepare::{PrepareParams, PrepareResult};
 use crate::utils;
-use crate::utils::contract;
+use crate::utils::fees::FeeStrategy;
+use crate::utils::{abi_args, contract};
 use ethers::abi::Abi;
 
 #[derive(Clone)]
@@ -22,39 +24,66 @@ struct AppState {
     result: Arc<Mutex<Option<PrepareResult>>>,
 }
 
+/// Values pre-filled into the form from command-line flags
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct DefaultParams {
-    contract: Option<String>,
-    rpc_url: Option<String>,
-    network: Option<String>,
-    infura_key: Option<String>,
-    from: Option<String>,
-    to: Option<String>,
-    function_name: Option<String>,
-    args: Option<String>,
-    value: String,
-    output: String,
-    gas_limit: Option<u64>,
+pub struct DefaultParams {
+    pub contract: Option<String>,
+    pub contract_name: Option<String>,
+    pub rpc_url: Option<String>,
+    pub network: Option<String>,
+    pub infura_key: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub function_name: Option<String>,
+    pub args: Vec<Value>,
+    pub value: String,
+    pub output: String,
+    pub gas_limit: Option<u64>,
+    pub gas_multiplier: f64,
+    pub fee_strategy: FeeStrategy,
+    pub max_fee: Option<String>,
+    pub priority_fee: Option<String>,
+    pub gas_price: Option<String>,
+    pub force: bool,
+    pub access_list: bool,
 }
 
 #[derive(Debug, Deserialize)]
 struct FormData {
-    contract: String,
+    /// Omitted for a plain transfer
+    contract: Option<String>,
+    /// Selects a contract from a multi-contract JSON file
+    contract_name: Option<String>,
     rpc_url: Option<String>,
     network: Option<String>,
     infura_key: Option<String>,
     from: String,
     to: Option<String>,
     function_name: Option<String>,
-    args: Option<String>,
+    /// One value per parameter field
+    #[serde(default)]
+    args: Vec<Value>,
+    /// Free-form fallback field (JSON array or Solidity literal list)
+    args_text: Option<String>,
     value: String,
     output: String,
     gas_limit: Option<u64>,
+    gas_multiplier: Option<f64>,
+    #[serde(default)]
+    fee_strategy: FeeStrategy,
+    max_fee: Option<String>,
+    priority_fee: Option<String>,
+    gas_price: Option<String>,
+    #[serde(default)]
+    force: bool,
+    #[serde(default)]
+    access_list: bool,
 }
 
 #[derive(Debug, Deserialize)]
 struct AbiRequest {
     contract: String,
+    contract_name: Option<String>,
     function_name: Option<String>,
 }
 
@@ -75,35 +104,12 @@ struct ParamInfo {
 #[derive(Debug, Serialize)]
 struct FunctionInfo {
     name: String,
+    /// Canonical signature, unique across overloads
+    signature: String,
+    selector: String,
 }
 
-pub async fn execute(
-    contract: Option<String>,
-    rpc_url: Option<String>,
-    network: Option<String>,
-    infura_key: Option<String>,
-    from: Option<String>,
-    to: Option<String>,
-    function_name: Option<String>,
-    args: Option<String>,
-    value: String,
-    output: String,
-    gas_limit: Option<u64>,
-) -> Result<()> {
-    let defaults = DefaultParams {
-        contract,
-        rpc_url,
-        network,
-        infura_key,
-        from,
-        to,
-        function_name,
-        args,
-        value,
-        output,
-        gas_limit,
-    };
-
+pub async fn execute(defaults: DefaultParams) -> Result<()> {
     // Find available port
     let listener = TcpListener::bind("127.0.0.1:0")
         .context("Failed to bind to localhost")?;
@@ -144,16 +150,27 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
     let defaults = &state.defaults;
 
     let contract_val = defaults.contract.as_deref().unwrap_or("");
+    let contract_name_val = defaults.contract_name.as_deref().unwrap_or("");
     let rpc_url_val = defaults.rpc_url.as_deref().unwrap_or("");
     let network_val = defaults.network.as_deref().unwrap_or("");
     let infura_key_val = defaults.infura_key.as_deref().unwrap_or("");
     let from_val = defaults.from.as_deref().unwrap_or("");
     let to_val = defaults.to.as_deref().unwrap_or("");
     let function_val = defaults.function_name.as_deref().unwrap_or("");
-    let args_val = defaults.args.as_deref().unwrap_or("");
+    let args_json = serde_json::to_string(&defaults.args).unwrap_or_else(|_| "[]".to_string());
     let value_val = &defaults.value;
     let output_val = &defaults.output;
     let gas_limit_val = defaults.gas_limit.map(|g| g.to_string()).unwrap_or_default();
+    let gas_multiplier_val = defaults.gas_multiplier;
+    let force_checked = if defaults.force { " checked" } else { "" };
+    let access_list_checked = if defaults.access_list { " checked" } else { "" };
+    let fee_strategy_val = serde_json::to_value(defaults.fee_strategy)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+    let max_fee_val = defaults.max_fee.as_deref().unwrap_or("");
+    let priority_fee_val = defaults.priority_fee.as_deref().unwrap_or("");
+    let gas_price_val = defaults.gas_price.as_deref().unwrap_or("");
 
     let html = format!(r#"
 <!DOCTYPE html>
@@ -227,7 +244,7 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             align-items: center;
             gap: 8px;
         }}
-        .radio-option input[type="radio"] {{
+        .radio-option input[type="radio"], .radio-option input[type="checkbox"] {{
             width: auto;
         }}
         button {{
@@ -343,10 +360,16 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             <div class="fieldset">
                 <legend>Contract & Account</legend>
 
-                <div class="form-group">
+                <div class="form-group" id="contract-group">
                     <label for="contract">Contract JSON Path:</label>
                     <input type="text" id="contract" name="contract" value="{contract_val}" placeholder="./Counter.json" required>
-                    <p class="help-text">Path to compiled Solidity contract JSON</p>
+                    <p class="help-text">Path to compiled Solidity contract JSON (Hardhat, Foundry, Truffle or solc output)</p>
+                </div>
+
+                <div class="form-group" id="contract-name-group">
+                    <label for="contract_name">Contract Name (optional):</label>
+                    <input type="text" id="contract_name" name="contract_name" value="{contract_name_val}" placeholder="Counter">
+                    <p class="help-text">Needed when the JSON holds several contracts; Name or path/File.sol:Name</p>
                 </div>
 
                 <div class="form-group">
@@ -370,17 +393,21 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
                             <input type="radio" id="mode-call" name="tx-mode" value="call">
                             <label for="mode-call" style="margin: 0;">Call Function</label>
                         </div>
+                        <div class="radio-option">
+                            <input type="radio" id="mode-transfer" name="tx-mode" value="transfer">
+                            <label for="mode-transfer" style="margin: 0;">Transfer</label>
+                        </div>
                     </div>
                 </div>
 
                 <div id="call-fields" class="conditional">
                     <div class="form-group">
-                        <label for="to">Contract Address (to):</label>
+                        <label for="to" id="to-label">Contract Address (to):</label>
                         <input type="text" id="to" name="to" value="{to_val}" placeholder="0x...">
-                        <p class="help-text">Deployed contract address to call</p>
+                        <p class="help-text" id="to-help">Deployed contract address to call</p>
                     </div>
 
-                    <div class="form-group">
+                    <div class="form-group" id="function-group">
                         <label for="function_name">Function Name:</label>
                         <select id="function_name" name="function_name">
                             <option value="">-- Select Function --</option>
@@ -393,9 +420,9 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
                 </div>
 
                 <div class="form-group" id="args-fallback">
-                    <label for="args">Arguments (comma-separated):</label>
-                    <input type="text" id="args" name="args" value="{args_val}" placeholder="0x123..., 1000000">
-                    <p class="help-text">Constructor args (deploy) or function args (call)</p>
+                    <label for="args">Arguments (JSON array or comma-separated):</label>
+                    <input type="text" id="args" name="args" value="" placeholder='["0x123...", 1000000]'>
+                    <p class="help-text">Constructor args (deploy) or function args (call); quote strings containing commas</p>
                 </div>
             </div>
 
@@ -410,14 +437,63 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
 
                 <div class="form-group">
                     <label for="gas_limit">Gas Limit (optional):</label>
-                    <input type="text" id="gas_limit" name="gas_limit" value="{gas_limit_val}" placeholder="3000000">
-                    <p class="help-text">Leave empty for default (3,000,000)</p>
+                    <input type="text" id="gas_limit" name="gas_limit" value="{gas_limit_val}" placeholder="estimate">
+                    <p class="help-text">Leave empty to estimate via eth_estimateGas</p>
+                </div>
+
+                <div class="form-group">
+                    <label for="gas_multiplier">Gas Multiplier:</label>
+                    <input type="text" id="gas_multiplier" name="gas_multiplier" value="{gas_multiplier_val}" placeholder="1.2">
+                    <p class="help-text">Safety multiplier applied to the estimated gas limit</p>
+                </div>
+
+                <div class="form-group">
+                    <label for="fee_strategy">Fee Strategy:</label>
+                    <select id="fee_strategy" name="fee_strategy">
+                        <option value="slow">Slow (10th percentile tip, 1.5x base fee)</option>
+                        <option value="normal">Normal (50th percentile tip, 2x base fee)</option>
+                        <option value="fast">Fast (90th percentile tip, 3x base fee)</option>
+                        <option value="node">Node (eth_maxPriorityFeePerGas tip, 2x base fee)</option>
+                    </select>
+                    <p class="help-text">Used for any fee not set explicitly below</p>
+                </div>
+
+                <div class="form-group">
+                    <label for="max_fee">Max Fee per Gas (gwei, optional):</label>
+                    <input type="text" id="max_fee" name="max_fee" value="{max_fee_val}" placeholder="strategy">
+                </div>
+
+                <div class="form-group">
+                    <label for="priority_fee">Max Priority Fee per Gas (gwei, optional):</label>
+                    <input type="text" id="priority_fee" name="priority_fee" value="{priority_fee_val}" placeholder="strategy">
+                </div>
+
+                <div class="form-group">
+                    <label for="gas_price">Legacy Gas Price (gwei, optional):</label>
+                    <input type="text" id="gas_price" name="gas_price" value="{gas_price_val}" placeholder="">
+                    <p class="help-text">Forces a legacy transaction; leave max fee and priority fee empty</p>
+                </div>
+
+                <div class="form-group">
+                    <div class="radio-option">
+                        <input type="checkbox" id="access_list" name="access_list"{access_list_checked}>
+                        <label for="access_list" style="margin: 0;">Attach an access list (EIP-2930)</label>
+                    </div>
+                    <p class="help-text">Generated with eth_createAccessList; can lower gas for storage-heavy calls</p>
                 </div>
 
                 <div class="form-group">
                     <label for="output">Output File:</label>
                     <input type="text" id="output" name="output" value="{output_val}" placeholder="unsigned.json">
                 </div>
+
+                <div class="form-group">
+                    <div class="radio-option">
+                        <input type="checkbox" id="force" name="force"{force_checked}>
+                        <label for="force" style="margin: 0;">Prepare even if the simulation reverts (requires a gas limit)</label>
+                    </div>
+                    <p class="help-text">The transaction is simulated with eth_call before the file is written</p>
+                </div>
             </div>
 
             <button type="submit">Prepare Transaction</button>
@@ -429,6 +505,7 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
     <script>
         let currentParams = [];
         let availableFunctions = [];
+        const prefilledArgs = {args_json};
 
         // Populate function dropdown with available functions
         function populateFunctionDropdown(functions) {{
@@ -438,16 +515,16 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             // Clear existing options except the first placeholder
             functionSelect.innerHTML = '<option value="">-- Select Function --</option>';
 
-            // Add function options
+            // Add one option per overload, keyed by its full signature
             functions.forEach(func => {{
                 const option = document.createElement('option');
-                option.value = func.name;
-                option.textContent = func.name;
+                option.value = func.signature;
+                option.textContent = `${{func.signature}}  [${{func.selector}}]`;
                 functionSelect.appendChild(option);
             }});
 
             // Restore previously selected value if it still exists
-            if (currentValue && functions.some(f => f.name === currentValue)) {{
+            if (currentValue && functions.some(f => f.signature === currentValue)) {{
                 functionSelect.value = currentValue;
             }}
 
@@ -469,6 +546,7 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
                     headers: {{ 'Content-Type': 'application/json' }},
                     body: JSON.stringify({{
                         contract: contractPath,
+                        contract_name: document.getElementById('contract_name').value,
                         function_name: null
                     }})
                 }});
@@ -522,6 +600,7 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
                     headers: {{ 'Content-Type': 'application/json' }},
                     body: JSON.stringify({{
                         contract: contractPath,
+                        contract_name: document.getElementById('contract_name').value,
                         function_name: functionName
                     }})
                 }});
@@ -567,8 +646,7 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             container.innerHTML = '';
 
             // Check if there are prefilled args from command line
-            const fallbackArgs = document.getElementById('args').value;
-            const prefilledValues = fallbackArgs ? fallbackArgs.split(',').map(v => v.trim()) : [];
+            const prefilledValues = prefilledArgs.map(v => typeof v === 'string' ? v : JSON.stringify(v));
 
             params.forEach((param, index) => {{
                 console.log(`Creating field for param ${{index}}:`, param.name, param.param_type);
@@ -608,6 +686,15 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             return '';
         }}
 
+        // Resolve a function given as name, signature or selector to its dropdown value.
+        // A bare name only resolves when it is not overloaded.
+        function resolveFunctionOption(spec) {{
+            const bySignature = availableFunctions.find(f => f.signature === spec || f.selector === spec.toLowerCase());
+            if (bySignature) return bySignature.signature;
+            const byName = availableFunctions.filter(f => f.name === spec);
+            return byName.length === 1 ? byName[0].signature : '';
+        }}
+
         // Toggle RPC method
         document.querySelectorAll('input[name="rpc-method"]').forEach(radio => {{
             radio.addEventListener('change', (e) => {{
@@ -621,10 +708,27 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             }});
         }});
 
+        // Show or hide the contract-specific fields (a plain transfer needs no contract)
+        function setTransferMode(isTransfer) {{
+            document.getElementById('contract-group').style.display = isTransfer ? 'none' : 'block';
+            document.getElementById('contract-name-group').style.display = isTransfer ? 'none' : 'block';
+            document.getElementById('contract').required = !isTransfer;
+            document.getElementById('function-group').style.display = isTransfer ? 'none' : 'block';
+            document.getElementById('to-label').textContent = isTransfer ? 'Recipient Address (to):' : 'Contract Address (to):';
+            document.getElementById('to-help').textContent = isTransfer ? 'Address receiving the ETH value' : 'Deployed contract address to call';
+        }}
+
         // Toggle transaction mode
         document.querySelectorAll('input[name="tx-mode"]').forEach(radio => {{
             radio.addEventListener('change', async (e) => {{
-                if (e.target.value === 'call') {{
+                setTransferMode(e.target.value === 'transfer');
+                if (e.target.value === 'transfer') {{
+                    document.getElementById('call-fields').classList.add('active');
+                    // A plain transfer takes no arguments
+                    document.getElementById('args-container').innerHTML = '';
+                    document.getElementById('args-fallback').style.display = 'none';
+                    currentParams = [];
+                }} else if (e.target.value === 'call') {{
                     document.getElementById('call-fields').classList.add('active');
                     // Clear constructor parameters when switching to call mode
                     document.getElementById('args-container').innerHTML = '';
@@ -648,7 +752,7 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             const txMode = document.querySelector('input[name="tx-mode"]:checked').value;
             if (txMode === 'call') {{
                 await loadAvailableFunctions();
-            }} else {{
+            }} else if (txMode === 'deploy') {{
                 await loadAbiParameters();
             }}
         }});
@@ -657,7 +761,16 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             const txMode = document.querySelector('input[name="tx-mode"]:checked').value;
             if (txMode === 'call') {{
                 await loadAvailableFunctions();
-            }} else {{
+            }} else if (txMode === 'deploy') {{
+                await loadAbiParameters();
+            }}
+        }});
+
+        document.getElementById('contract_name').addEventListener('change', async () => {{
+            const txMode = document.querySelector('input[name="tx-mode"]:checked').value;
+            if (txMode === 'call') {{
+                await loadAvailableFunctions();
+            }} else if (txMode === 'deploy') {{
                 await loadAbiParameters();
             }}
         }});
@@ -665,6 +778,9 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
         // Load function parameters when function is selected
         document.getElementById('function_name').addEventListener('change', loadAbiParameters);
 
+        // Pre-select fee strategy
+        document.getElementById('fee_strategy').value = '{fee_strategy_val}';
+
         // Pre-select network if provided
         const networkSelect = document.getElementById('network');
         if ('{network_val}') {{
@@ -674,8 +790,13 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             document.getElementById('infura-rpc').classList.add('active');
         }}
 
-        // Pre-select call mode if to/function provided
-        if ('{to_val}' || '{function_val}') {{
+        // Pre-select transfer mode if only a recipient was provided, call mode if to/function provided
+        if ('{to_val}' && !'{function_val}' && !'{contract_val}') {{
+            document.getElementById('mode-transfer').checked = true;
+            document.getElementById('call-fields').classList.add('active');
+            document.getElementById('args-fallback').style.display = 'none';
+            setTransferMode(true);
+        }} else if ('{to_val}' || '{function_val}') {{
             document.getElementById('mode-call').checked = true;
             document.getElementById('call-fields').classList.add('active');
             // Hide fallback when in call mode
@@ -687,7 +808,10 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             const txMode = document.querySelector('input[name="tx-mode"]:checked').value;
 
             // Check if args were provided via command line
-            const hasPrefilledArgs = '{args_val}' !== '';
+            const hasPrefilledArgs = prefilledArgs.length > 0;
+            if (hasPrefilledArgs) {{
+                document.getElementById('args').value = JSON.stringify(prefilledArgs);
+            }}
 
             // Hide fallback initially unless args were prefilled via command line
             if (!hasPrefilledArgs) {{
@@ -701,7 +825,7 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
 
                     // Pre-select function if provided
                     if ('{function_val}') {{
-                        document.getElementById('function_name').value = '{function_val}';
+                        document.getElementById('function_name').value = resolveFunctionOption('{function_val}');
                         // Load parameters for the selected function
                         await loadAbiParameters();
                     }}
@@ -721,7 +845,6 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             const txMode = formData.get('tx-mode');
 
             const data = {{
-                contract: formData.get('contract'),
                 from: formData.get('from'),
                 value: formData.get('value') || '0',
                 output: formData.get('output') || 'unsigned.json',
@@ -736,9 +859,15 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
             }}
 
             // Transaction mode
+            if (txMode !== 'transfer') {{
+                data.contract = formData.get('contract');
+                data.contract_name = formData.get('contract_name');
+            }}
             if (txMode === 'call') {{
                 data.to = formData.get('to');
                 data.function_name = formData.get('function_name');
+            }} else if (txMode === 'transfer') {{
+                data.to = formData.get('to');
             }}
 
             // Optional fields - collect individual parameter values
@@ -748,16 +877,29 @@ async fn serve_form(State(state): State<AppState>) -> Html<String> {
                     return input ? input.value.trim() : '';
                 }});
                 if (paramValues.some(v => v !== '')) {{
-                    data.args = paramValues.join(', ');
+                    data.args = paramValues;
                 }}
             }} else {{
                 const args = formData.get('args');
-                if (args) data.args = args;
+                if (args) data.args_text = args;
             }}
 
             const gasLimit = formData.get('gas_limit');
             if (gasLimit) data.gas_limit = parseInt(gasLimit);
 
+            const gasMultiplier = formData.get('gas_multiplier');
+            if (gasMultiplier) data.gas_multiplier = parseFloat(gasMultiplier);
+
+            data.fee_strategy = formData.get('fee_strategy');
+            const maxFee = formData.get('max_fee');
+            if (maxFee) data.max_fee = maxFee;
+            const priorityFee = formData.get('priority_fee');
+            if (priorityFee) data.priority_fee = priorityFee;
+            const gasPrice = formData.get('gas_price');
+            if (gasPrice) data.gas_price = gasPrice;
+            data.force = formData.get('force') === 'on';
+            data.access_list = formData.get('access_list') === 'on';
+
             const resultDiv = document.getElementById('result');
             resultDiv.style.display = 'block';
             resultDiv.innerHTML = '<p style="color: #58a6ff;">⏳ Preparing transaction...</p>';
@@ -812,17 +954,18 @@ async fn handle_abi(
     Json(req): Json<AbiRequest>,
 ) -> impl IntoResponse {
     // Parse contract JSON to get ABI
-    let result = contract::parse_contract_json(&req.contract);
+    let contract_name = req.contract_name.as_deref().filter(|name| !name.is_empty());
+    let result = contract::parse_contract_json(&req.contract, contract_name);
 
     match result {
-        Ok((_bytecode, abi_value)) => {
-            let abi: Result<Abi, _> = serde_json::from_value(abi_value);
+        Ok(artifact) => {
+            let abi: Result<Abi, _> = serde_json::from_value(artifact.abi);
 
             match abi {
                 Ok(abi) => {
                     let params = if let Some(func_name) = &req.function_name {
                         // Get function parameters
-                        if let Ok(function) = abi.function(func_name) {
+                        if let Ok(function) = contract::find_function(&abi, func_name) {
                             function.inputs.iter().map(|p| ParamInfo {
                                 name: p.name.clone(),
                                 param_type: format!("{}", p.kind),
@@ -850,6 +993,8 @@ async fn handle_abi(
                         })
                         .map(|f| FunctionInfo {
                             name: f.name.clone(),
+                            signature: contract::canonical_signature(f),
+                            selector: format!("0x{}", hex::encode(f.short_signature())),
                         })
                         .collect();
 
@@ -905,16 +1050,57 @@ async fn handle_prepare(
         }
     };
 
+    let args = match form_data.args_text {
+        Some(text) if form_data.args.is_empty() => match abi_args::parse_args_input(&text) {
+            Ok(args) => args,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "success": false,
+                        "error": format!("Failed to parse arguments: {:#}", e)
+                    }))
+                );
+            }
+        },
+        _ => form_data.args,
+    };
+
     let params = PrepareParams {
-        contract: form_data.contract,
-        rpc_url,
+        contract: form_data.contract.filter(|c| !c.is_empty()),
+        contract_name: form_data.contract_name.filter(|name| !name.is_empty()),
+        rpc_url: Some(rpc_url),
         from: form_data.from,
         to: form_data.to,
         function_name: form_data.function_name,
-        args: form_data.args,
+        sig: None,
+        data: None,
+        args,
         value: form_data.value,
         output: form_data.output,
         gas_limit: form_data.gas_limit,
+        gas_multiplier: form_data
+            .gas_multiplier
+            .unwrap_or(utils::gas::DEFAULT_GAS_MULTIPLIER),
+        fee_strategy: form_data.fee_strategy,
+        max_fee: form_data.max_fee,
+        priority_fee: form_data.priority_fee,
+        gas_price: form_data.gas_price,
+        offline: false,
+        chain_id: None,
+        nonce: None,
+        force: form_data.force,
+        access_list: form_data.access_list,
+        blobs: Vec::new(),
+        blob_encoding: Default::default(),
+        blob_proofs: Default::default(),
+        max_blob_fee: None,
+        delegate: None,
+        create2: false,
+        salt: None,
+        factory: None,
+        links: Vec::new(),
+        multicall: None,
     };
 
     match super::prepare::run(params).await {
@@ -3,22 +3,87 @@ use ethers::{
     prelude::*,
     signers::Signer,
     types::transaction::eip2718::TypedTransaction,
+    utils::get_contract_address,
 };
 use std::fs;
 
-use crate::types::prepare_output::UnsignedTransaction;
-use crate::types::sign_output::SignedTransaction;
+use crate::types::prepare_output::{
+    Authorization, BlobSidecar, Create2Deployment, MulticallCall, UnsignedBundle, UnsignedTransaction,
+};
+use crate::types::sign_output::{SignedBundle, SignedTransaction};
+use crate::utils::{blob, chains, contract, metadata, multicall, review, units};
+use crate::utils::typed_tx::{self, BlobTransaction, SetCodeTransaction, SignedAuthorization};
 
-pub async fn execute(unsigned_path: String, keystore_path: String, output: String) -> Result<()> {
+pub async fn execute(
+    unsigned_path: String,
+    keystore_path: String,
+    output: String,
+    abi_path: Option<String>,
+    yes: bool,
+) -> Result<()> {
     println!("Signing transaction...");
     println!("Loading unsigned transaction from: {}", unsigned_path);
 
-    // Load unsigned transaction
+    // Load unsigned transaction, or a bundle of them
     let unsigned_json = fs::read_to_string(&unsigned_path)
         .context("Failed to read unsigned transaction file")?;
 
-    let unsigned_tx: UnsignedTransaction = serde_json::from_str(&unsigned_json)
+    let unsigned_value: serde_json::Value = serde_json::from_str(&unsigned_json)
         .context("Failed to parse unsigned transaction JSON")?;
+    let (bundle, transactions): (Option<UnsignedBundle>, Vec<UnsignedTransaction>) =
+        if unsigned_value.get("transactions").is_some() {
+            let bundle: UnsignedBundle = serde_json::from_value(unsigned_value)
+                .context("Failed to parse unsigned transaction bundle")?;
+            check_bundle(&bundle)?;
+            let transactions = bundle.transactions.clone();
+            (Some(bundle), transactions)
+        } else {
+            let unsigned_tx = serde_json::from_value(unsigned_value)
+                .context("Failed to parse unsigned transaction JSON")?;
+            (None, vec![unsigned_tx])
+        };
+
+    let sender = prepared_sender(&transactions)?;
+    let abi = abi_path.as_deref().map(review::load_abi).transpose()?;
+
+    for (i, unsigned_tx) in transactions.iter().enumerate() {
+        if bundle.is_some() {
+            println!("\nTransaction {}/{} (nonce {})", i + 1, transactions.len(), unsigned_tx.nonce);
+        }
+        check_unsigned(unsigned_tx)?;
+        review::print_review(unsigned_tx, abi.as_ref())?;
+    }
+
+    // Nothing is signed until the operator has confirmed what they reviewed
+    if transactions.len() > 1 {
+        let total = transactions
+            .iter()
+            .try_fold(U256::zero(), |total, tx| {
+                total
+                    .checked_add(review::max_cost(tx)?)
+                    .context("Max total cost of the bundle overflows 256 bits")
+            })?;
+        println!(
+            "\nMax total cost of all {} transactions: {} {}",
+            transactions.len(),
+            units::format_ether(total),
+            chains::native_symbol(transactions[0].chain_id)
+        );
+    }
+    if yes {
+        println!("\nSkipping confirmation (--yes)");
+    } else {
+        let mut prompt = match transactions.len() {
+            1 => "Type 'yes' to sign this transaction".to_string(),
+            n => format!("Type 'yes' to sign these {} transactions", n),
+        };
+        // EIP-7702: the account signs over its own code, so the delegation is confirmed too
+        if transactions.iter().any(|tx| tx.authorization_list.is_some()) {
+            prompt.push_str(" and the EIP-7702 delegation");
+        }
+        prompt.push(':');
+        review::confirm(&prompt, "Not confirmed; nothing was signed")?;
+    }
 
     // Prompt for password
     println!("Enter keystore password:");
@@ -33,15 +98,195 @@ pub async fn execute(unsigned_path: String, keystore_path: String, output: Strin
     println!("Keystore loaded successfully!");
     println!("Address: {:?}", wallet.address());
 
+    // The nonces were assigned to the prepared sender, so no other key may sign
+    check_sender(sender, wallet.address())?;
+
+    let mut signed_txs = Vec::with_capacity(transactions.len());
+    for unsigned_tx in &transactions {
+        signed_txs.push(sign_unsigned(unsigned_tx, &wallet).await?);
+    }
+
+    // Save to output file
+    println!("Saving signed transaction to: {}", output);
+    let json = match bundle {
+        Some(bundle) => serde_json::to_string_pretty(&SignedBundle {
+            chain_id: bundle.chain_id,
+            from: format!("{:?}", wallet.address()),
+            rpc_url: bundle.rpc_url,
+            transactions: signed_txs.clone(),
+        }),
+        None => serde_json::to_string_pretty(&signed_txs[0]),
+    }
+    .context("Failed to serialize signed transaction")?;
+
+    fs::write(&output, json)
+        .context("Failed to write output file")?;
+
+    if let [signed_tx] = signed_txs.as_slice() {
+        println!("\n✓ Transaction signed successfully!");
+        println!("  Transaction hash: {}", signed_tx.transaction_hash);
+        println!("  From: {}", signed_tx.from);
+        println!("  Nonce: {}", signed_tx.nonce);
+        if let Some(ref address) = signed_tx.contract_address {
+            println!("  Contract address: {}", address);
+        }
+    } else {
+        println!("\n✓ {} transactions signed successfully!", signed_txs.len());
+        println!("  From: {:?}", wallet.address());
+        for signed_tx in &signed_txs {
+            println!("  Nonce {}: {}", signed_tx.nonce, signed_tx.transaction_hash);
+            if let Some(ref address) = signed_tx.contract_address {
+                println!("    Contract address: {}", address);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A bundle is signed for one chain, with consecutive nonces so that no transaction
+/// waits on a nonce that is never sent
+pub fn check_bundle(bundle: &UnsignedBundle) -> Result<()> {
+    if bundle.transactions.is_empty() {
+        anyhow::bail!("Bundle contains no transactions");
+    }
+    for (i, tx) in bundle.transactions.iter().enumerate() {
+        if tx.chain_id != bundle.chain_id {
+            anyhow::bail!(
+                "Transaction {} is for chain {}, but the bundle is for chain {}",
+                i + 1,
+                tx.chain_id,
+                bundle.chain_id
+            );
+        }
+        if tx.from != bundle.transactions[0].from {
+            anyhow::bail!("Transaction {} has a different sender than transaction 1", i + 1);
+        }
+        if tx.nonce != bundle.transactions[0].nonce + i as u64 {
+            anyhow::bail!("Bundle nonces are not consecutive at transaction {} (nonce {})", i + 1, tx.nonce);
+        }
+    }
+    Ok(())
+}
+
+/// The account the transactions were prepared for; without it the keystore cannot be
+/// checked, so the file is refused before anything is reviewed
+fn prepared_sender(transactions: &[UnsignedTransaction]) -> Result<Address> {
+    let from = transactions[0].from.as_deref().context(
+        "The unsigned file does not record its sender (from), so the keystore cannot be checked against it; run prepare again",
+    )?;
+    from.parse().context("Invalid from address")
+}
+
+/// Refuse to sign when the keystore is not the account the transactions were prepared for
+fn check_sender(from: Address, address: Address) -> Result<()> {
+    if from != address {
+        anyhow::bail!(
+            "The transaction was prepared for {:?}, but this keystore is {:?}; nothing was signed",
+            from,
+            address
+        );
+    }
+    println!("Keystore matches the prepared sender");
+    Ok(())
+}
+
+/// Checks that need no key: the blobs and CREATE2 address must follow from what will
+/// be signed
+pub fn check_unsigned(unsigned_tx: &UnsignedTransaction) -> Result<()> {
+    // The blobs must match the versioned hashes that will be signed
+    if let Some(ref sidecar) = unsigned_tx.blob_sidecar {
+        println!("Verifying blob commitments and proofs...");
+        blob::verify_sidecar(
+            sidecar,
+            unsigned_tx.blob_versioned_hashes.as_deref().unwrap_or_default(),
+        )?;
+        println!("Blob data verified ({} blob(s))", sidecar.blobs.len());
+    }
+
+    // The CREATE2 address must follow from the calldata that will be signed
+    if let Some(ref deployment) = unsigned_tx.create2 {
+        verify_create2(unsigned_tx, deployment)?;
+        println!("CREATE2 deployment via factory {}", deployment.factory);
+    }
+    if let Some(ref address) = unsigned_tx.contract_address {
+        println!("Contract address: {}", address);
+    }
+
+    // The decoding metadata is only shown once it is known to describe the calldata
+    if let Some(ref decoded) = unsigned_tx.decoded {
+        let data = hex::decode(&unsigned_tx.data).context("Invalid transaction data")?;
+        metadata::verify(decoded, &data)?;
+        println!("Decoding metadata matches the transaction data");
+    }
+
+    // The listed calls must be exactly the ones the Multicall3 calldata makes
+    if let Some(ref calls) = unsigned_tx.multicall {
+        verify_multicall(unsigned_tx, calls)?;
+        println!("Multicall3 calldata matches its {} listed call(s)", calls.len());
+    }
+    Ok(())
+}
+
+/// Sign one transaction with the decrypted keystore
+async fn sign_unsigned(unsigned_tx: &UnsignedTransaction, wallet: &LocalWallet) -> Result<SignedTransaction> {
+    // A CREATE address depends on the sender, so a different key deploys elsewhere
+    if let (None, None, Some(address)) = (&unsigned_tx.to, &unsigned_tx.create2, &unsigned_tx.contract_address) {
+        let address: Address = address.parse().context("Invalid contract address")?;
+        let expected = get_contract_address(wallet.address(), unsigned_tx.nonce);
+        if expected != address {
+            anyhow::bail!(
+                "This keystore would deploy to {:?} at nonce {}, not {:?}; the transaction was prepared for a different sender",
+                expected,
+                unsigned_tx.nonce,
+                address
+            );
+        }
+    }
+
+    // Build and sign the transaction; blob transactions need their own encoding
+    println!("Signing transaction...");
+    let (rlp_signed, tx_hash) = if let Some(ref sidecar) = unsigned_tx.blob_sidecar {
+        sign_blob_transaction(unsigned_tx, sidecar, wallet)?
+    } else if let Some(ref authorizations) = unsigned_tx.authorization_list {
+        sign_set_code_transaction(unsigned_tx, authorizations, wallet)?
+    } else {
+        sign_typed_transaction(unsigned_tx, wallet).await?
+    };
+    let raw_transaction = hex::encode(&rlp_signed);
+
+    // Create signed transaction output
+    Ok(SignedTransaction {
+        raw_transaction: format!("0x{}", raw_transaction),
+        transaction_hash: format!("{:?}", tx_hash),
+        from: format!("{:?}", wallet.address()),
+        to: unsigned_tx.to.clone(),
+        nonce: unsigned_tx.nonce,
+        chain_id: unsigned_tx.chain_id,
+        contract_address: unsigned_tx.contract_address.clone(),
+        rpc_url: unsigned_tx.rpc_url.clone(),
+    })
+}
+
+/// Build a legacy, EIP-2930 or EIP-1559 transaction with ethers and sign it.
+/// Returns the raw signed transaction and its hash.
+async fn sign_typed_transaction(
+    unsigned_tx: &UnsignedTransaction,
+    wallet: &LocalWallet,
+) -> Result<(Vec<u8>, H256)> {
     // Build transaction
-    let mut tx: TypedTransaction = if unsigned_tx.max_fee_per_gas.is_some() {
+    let mut tx: TypedTransaction = if let Some(max_fee_per_gas) = unsigned_tx.max_fee_per_gas {
         // EIP-1559 transaction
         let mut eip1559 = Eip1559TransactionRequest::new();
         eip1559 = eip1559.chain_id(unsigned_tx.chain_id);
         eip1559 = eip1559.nonce(unsigned_tx.nonce);
         eip1559 = eip1559.gas(unsigned_tx.gas_limit);
-        eip1559 = eip1559.max_fee_per_gas(unsigned_tx.max_fee_per_gas.unwrap());
-        eip1559 = eip1559.max_priority_fee_per_gas(unsigned_tx.max_priority_fee_per_gas.unwrap());
+        eip1559 = eip1559.max_fee_per_gas(max_fee_per_gas);
+        eip1559 = eip1559.max_priority_fee_per_gas(
+            unsigned_tx
+                .max_priority_fee_per_gas
+                .context("max_priority_fee_per_gas is required when max_fee_per_gas is set")?,
+        );
 
         if let Some(ref to) = unsigned_tx.to {
             let to_addr: Address = to.parse()
@@ -53,9 +298,11 @@ pub async fn execute(unsigned_path: String, keystore_path: String, output: Strin
             .context("Failed to decode transaction data")?;
         eip1559 = eip1559.data(data);
 
-        let value: U256 = unsigned_tx.value.parse()
-            .context("Failed to parse value")?;
-        eip1559 = eip1559.value(value);
+        eip1559 = eip1559.value(unsigned_tx.value);
+
+        if let Some(ref access_list) = unsigned_tx.access_list {
+            eip1559 = eip1559.access_list(access_list.clone());
+        }
 
         TypedTransaction::Eip1559(eip1559)
     } else {
@@ -64,7 +311,11 @@ pub async fn execute(unsigned_path: String, keystore_path: String, output: Strin
         legacy = legacy.chain_id(unsigned_tx.chain_id);
         legacy = legacy.nonce(unsigned_tx.nonce);
         legacy = legacy.gas(unsigned_tx.gas_limit);
-        legacy = legacy.gas_price(unsigned_tx.gas_price.unwrap());
+        legacy = legacy.gas_price(
+            unsigned_tx
+                .gas_price
+                .context("Unsigned transaction has neither gas_price nor max_fee_per_gas")?,
+        );
 
         if let Some(ref to) = unsigned_tx.to {
             let to_addr: Address = to.parse()
@@ -76,15 +327,19 @@ pub async fn execute(unsigned_path: String, keystore_path: String, output: Strin
             .context("Failed to decode transaction data")?;
         legacy = legacy.data(data);
 
-        let value: U256 = unsigned_tx.value.parse()
-            .context("Failed to parse value")?;
-        legacy = legacy.value(value);
+        legacy = legacy.value(unsigned_tx.value);
 
-        TypedTransaction::Legacy(legacy)
+        // Legacy fees with an access list make an EIP-2930 (type-1) transaction
+        match unsigned_tx.access_list {
+            Some(ref access_list) => TypedTransaction::Eip2930(Eip2930TransactionRequest::new(
+                legacy,
+                access_list.clone(),
+            )),
+            None => TypedTransaction::Legacy(legacy),
+        }
     };
 
     // Sign transaction
-    println!("Signing transaction...");
     let signature = wallet.sign_transaction(&tx)
         .await
         .context("Failed to sign transaction")?;
@@ -94,35 +349,184 @@ pub async fn execute(unsigned_path: String, keystore_path: String, output: Strin
 
     // Encode the signed transaction
     let rlp_signed = tx.rlp_signed(&signature);
-    let raw_transaction = hex::encode(&rlp_signed);
 
     // Calculate transaction hash
     let tx_hash = ethers::utils::keccak256(&rlp_signed);
-    let transaction_hash = format!("0x{}", hex::encode(tx_hash));
 
-    // Create signed transaction output
-    let signed_tx = SignedTransaction {
-        raw_transaction: format!("0x{}", raw_transaction),
-        transaction_hash: transaction_hash.clone(),
-        from: format!("{:?}", wallet.address()),
-        to: unsigned_tx.to.clone(),
-        nonce: unsigned_tx.nonce,
+    Ok((rlp_signed.to_vec(), H256::from(tx_hash)))
+}
+
+/// Build and sign an EIP-4844 blob transaction. Returns the network form (signed
+/// transaction wrapped with the blob sidecar) and the transaction hash.
+fn sign_blob_transaction(
+    unsigned_tx: &UnsignedTransaction,
+    sidecar: &BlobSidecar,
+    wallet: &LocalWallet,
+) -> Result<(Vec<u8>, H256)> {
+    let to = unsigned_tx
+        .to
+        .as_deref()
+        .context("Blob transactions require a 'to' address")?
+        .parse()
+        .context("Invalid to address")?;
+    let data = hex::decode(&unsigned_tx.data)
+        .context("Failed to decode transaction data")?;
+
+    let tx = BlobTransaction {
         chain_id: unsigned_tx.chain_id,
-        rpc_url: unsigned_tx.rpc_url.clone(),
+        nonce: unsigned_tx.nonce,
+        max_priority_fee_per_gas: unsigned_tx
+            .max_priority_fee_per_gas
+            .context("Blob transactions require max_priority_fee_per_gas")?,
+        max_fee_per_gas: unsigned_tx
+            .max_fee_per_gas
+            .context("Blob transactions require max_fee_per_gas")?,
+        gas_limit: unsigned_tx.gas_limit,
+        to,
+        value: unsigned_tx.value,
+        data: data.into(),
+        access_list: unsigned_tx.access_list.clone().unwrap_or_default(),
+        max_fee_per_blob_gas: unsigned_tx
+            .max_fee_per_blob_gas
+            .context("Blob transactions require max_fee_per_blob_gas")?,
+        blob_versioned_hashes: unsigned_tx
+            .blob_versioned_hashes
+            .clone()
+            .context("Blob transactions require blob_versioned_hashes")?,
     };
 
-    // Save to output file
-    println!("Saving signed transaction to: {}", output);
-    let json = serde_json::to_string_pretty(&signed_tx)
-        .context("Failed to serialize signed transaction")?;
+    let signature = wallet
+        .sign_hash(tx.sighash())
+        .context("Failed to sign transaction")?;
 
-    fs::write(&output, json)
-        .context("Failed to write output file")?;
+    // The hash covers the signed transaction only, not the sidecar
+    let tx_hash = ethers::utils::keccak256(tx.rlp_signed(&signature));
+
+    Ok((tx.rlp_network(&signature, sidecar).to_vec(), H256::from(tx_hash)))
+}
+
+/// Recompute the CREATE2 address from the factory call, so an edited file cannot
+/// show one address while deploying to another
+fn verify_create2(unsigned_tx: &UnsignedTransaction, deployment: &Create2Deployment) -> Result<()> {
+    let factory: Address = deployment.factory.parse().context("Invalid CREATE2 factory address")?;
+    let expected: Address = unsigned_tx
+        .contract_address
+        .as_deref()
+        .context("CREATE2 transaction has no contract address")?
+        .parse()
+        .context("Invalid contract address")?;
+    let to: Option<Address> = unsigned_tx
+        .to
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .context("Invalid to address")?;
+    if to != Some(factory) {
+        anyhow::bail!("CREATE2 transaction is not sent to its factory {}", deployment.factory);
+    }
 
-    println!("\n✓ Transaction signed successfully!");
-    println!("  Transaction hash: {}", transaction_hash);
-    println!("  From: {}", signed_tx.from);
-    println!("  Nonce: {}", signed_tx.nonce);
+    let data = hex::decode(&unsigned_tx.data).context("Invalid transaction data")?;
+    let (salt, init_code) = match data.split_at_checked(32) {
+        Some((salt, init_code)) if salt == deployment.salt.as_bytes() => (deployment.salt, init_code),
+        _ => anyhow::bail!("CREATE2 calldata does not start with the salt {:?}", deployment.salt),
+    };
+    let address = contract::create2_address(factory, salt, init_code);
+    if address != expected {
+        anyhow::bail!(
+            "CREATE2 address mismatch: the calldata deploys to {:?}, not {:?}",
+            address,
+            expected
+        );
+    }
+    Ok(())
+}
 
+/// Re-encode the Multicall3 call from the listed calls and check each call's function
+/// label, so an edited file cannot list one set of calls while making another
+fn verify_multicall(unsigned_tx: &UnsignedTransaction, calls: &[MulticallCall]) -> Result<()> {
+    if unsigned_tx.to.is_none() {
+        anyhow::bail!("Multicall3 transaction has no 'to' address");
+    }
+    let data = hex::decode(&unsigned_tx.data).context("Invalid transaction data")?;
+    if multicall::encode(calls)? != data {
+        anyhow::bail!("Multicall3 calldata does not match the listed calls");
+    }
+    // The review shows each call as its label, so the label must name the called function
+    for call in calls {
+        multicall::decode_call(call)?;
+    }
+    let total = multicall::total_value(calls)?;
+    if total != unsigned_tx.value {
+        anyhow::bail!(
+            "Transaction sends {} wei, but the listed calls forward {} wei",
+            unsigned_tx.value,
+            total
+        );
+    }
     Ok(())
 }
+
+/// Sign the EIP-7702 authorizations and the set-code (type-4) transaction carrying them.
+/// Returns the raw signed transaction and its hash.
+fn sign_set_code_transaction(
+    unsigned_tx: &UnsignedTransaction,
+    authorizations: &[Authorization],
+    wallet: &LocalWallet,
+) -> Result<(Vec<u8>, H256)> {
+    let authorization_list = authorizations
+        .iter()
+        .map(|authorization| {
+            let address: Address = authorization
+                .address
+                .parse()
+                .context("Invalid delegate address in authorization")?;
+            let signature = wallet
+                .sign_hash(typed_tx::authorization_sighash(
+                    authorization.chain_id,
+                    address,
+                    authorization.nonce,
+                ))
+                .context("Failed to sign authorization")?;
+            Ok(SignedAuthorization {
+                chain_id: authorization.chain_id,
+                address,
+                nonce: authorization.nonce,
+                signature,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let to = unsigned_tx
+        .to
+        .as_deref()
+        .context("Set-code transactions require a 'to' address")?
+        .parse()
+        .context("Invalid to address")?;
+    let data = hex::decode(&unsigned_tx.data)
+        .context("Failed to decode transaction data")?;
+
+    let tx = SetCodeTransaction {
+        chain_id: unsigned_tx.chain_id,
+        nonce: unsigned_tx.nonce,
+        max_priority_fee_per_gas: unsigned_tx
+            .max_priority_fee_per_gas
+            .context("Set-code transactions require max_priority_fee_per_gas")?,
+        max_fee_per_gas: unsigned_tx
+            .max_fee_per_gas
+            .context("Set-code transactions require max_fee_per_gas")?,
+        gas_limit: unsigned_tx.gas_limit,
+        to,
+        value: unsigned_tx.value,
+        data: data.into(),
+        access_list: unsigned_tx.access_list.clone().unwrap_or_default(),
+        authorization_list,
+    };
+
+    let signature = wallet
+        .sign_hash(tx.sighash())
+        .context("Failed to sign transaction")?;
+    let rlp_signed = tx.rlp_signed(&signature);
+    let tx_hash = ethers::utils::keccak256(&rlp_signed);
+
+    Ok((rlp_signed.to_vec(), H256::from(tx_hash)))
+}
@@ -7,3 +7,14 @@
 /// - /0 (external chain)
 /// - /0 (address index 0)
 pub const DEFAULT_ETH_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Deterministic deployment proxy used for CREATE2 deployments by default
+///
+/// Deployed at the same address on most EVM chains. Its calldata is the 32-byte
+/// salt followed by the init code, and it returns the address of the new contract.
+pub const DEFAULT_CREATE2_FACTORY: &str = "0x4e59b44847b379578588920cA78FbF26c0B4956C";
+
+/// Multicall3, deployed at the same address on most EVM chains
+///
+/// Calls made through it have Multicall3, not the sender, as `msg.sender`.
+pub const DEFAULT_MULTICALL3: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
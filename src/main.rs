@@ -6,6 +6,13 @@ mod constants;
 mod types;
 mod utils;
 
+use commands::prepare::PrepareParams;
+use commands::prepare_bundle::BundleParams;
+use commands::prepare_forge::ForgeImportParams;
+use commands::prepare_interactive::DefaultParams;
+use utils::blob::{BlobEncoding, BlobProofs};
+use utils::fees::FeeStrategy;
+
 #[derive(Parser)]
 #[command(name = "cold-sign")]
 #[command(about = "Offline signer for EVM-compatible transactions", long_about = None)]
@@ -16,6 +23,7 @@ struct Cli {
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     /// Generate a new 24-word BIP39 mnemonic phrase (display only, nothing saved to disk)
     GenerateMnemonic,
@@ -41,10 +49,14 @@ enum Commands {
         #[arg(long)]
         interactive: bool,
 
-        /// Path to compiled contract JSON (Solidity compiler output)
-        #[arg(short, long, required_unless_present = "interactive")]
+        /// Path to compiled contract JSON (Solidity compiler output; omit for a plain transfer)
+        #[arg(short, long, required_unless_present_any = ["interactive", "to", "from_forge", "bundle", "multicall"])]
         contract: Option<String>,
 
+        /// Contract to use when the JSON holds several (e.g. solc standard JSON): Name or path/File.sol:Name
+        #[arg(long, requires = "contract")]
+        contract_name: Option<String>,
+
         /// RPC endpoint URL (use this OR --network with --infura-key)
         #[arg(short, long, conflicts_with_all = ["network", "infura_key"])]
         rpc_url: Option<String>,
@@ -58,21 +70,37 @@ enum Commands {
         infura_key: Option<String>,
 
         /// Sender address
-        #[arg(short, long, required_unless_present = "interactive")]
+        #[arg(short, long, required_unless_present_any = ["interactive", "from_forge"])]
         from: Option<String>,
 
-        /// Deployed contract address to call (enables call mode, must be used with --function)
-        #[arg(long, requires = "function_name")]
+        /// Deployed contract address to call (with --function), or recipient of a plain transfer (without --contract)
+        #[arg(long)]
         to: Option<String>,
 
-        /// Function name to call (enables call mode, must be used with --to)
-        #[arg(long = "function", requires = "to")]
+        /// Function name to call (enables call mode, must be used with --to and --contract)
+        #[arg(long = "function", requires_all = ["to", "contract"])]
         function_name: Option<String>,
 
-        /// Constructor or function arguments (comma-separated)
-        #[arg(long)]
+        /// Human-readable function signature to call without an artifact, e.g. "transfer(address,uint256)"
+        #[arg(long, requires = "to", conflicts_with_all = ["interactive", "contract", "function_name", "data"])]
+        sig: Option<String>,
+
+        /// Raw calldata (hex) to send to --to without an artifact
+        #[arg(long, requires = "to", conflicts_with_all = ["interactive", "contract", "function_name", "args", "arg", "args_file"])]
+        data: Option<String>,
+
+        /// Constructor or function arguments: a JSON array or Solidity literals separated by top-level commas
+        #[arg(long, conflicts_with_all = ["arg", "args_file"])]
         args: Option<String>,
 
+        /// A single constructor or function argument (repeat once per argument, in order)
+        #[arg(long, conflicts_with = "args_file")]
+        arg: Vec<String>,
+
+        /// File containing the arguments (JSON array or Solidity literal list)
+        #[arg(long)]
+        args_file: Option<String>,
+
         /// ETH value to send in wei (default: 0, for payable constructors or functions)
         #[arg(long, default_value = "0")]
         value: String,
@@ -81,9 +109,106 @@ enum Commands {
         #[arg(short, long, default_value = "unsigned.json")]
         output: String,
 
-        /// Gas limit (optional, defaults to 3,000,000 if not provided)
+        /// Gas limit (optional, estimated via eth_estimateGas if not provided)
         #[arg(long)]
         gas_limit: Option<u64>,
+
+        /// Safety multiplier applied to the estimated gas limit
+        #[arg(long, default_value_t = utils::gas::DEFAULT_GAS_MULTIPLIER, conflicts_with = "gas_limit")]
+        gas_multiplier: f64,
+
+        /// Fee strategy used to derive EIP-1559 fees from eth_feeHistory (or eth_maxPriorityFeePerGas for "node")
+        #[arg(long, value_enum, default_value_t = FeeStrategy::Normal)]
+        fee_strategy: FeeStrategy,
+
+        /// Max fee per gas in gwei (overrides the fee strategy; accepts wei/gwei/ether suffixes)
+        #[arg(long, conflicts_with = "gas_price")]
+        max_fee: Option<String>,
+
+        /// Max priority fee per gas in gwei (overrides the fee strategy; accepts wei/gwei/ether suffixes)
+        #[arg(long, conflicts_with = "gas_price")]
+        priority_fee: Option<String>,
+
+        /// Legacy gas price in gwei (forces a legacy transaction; accepts wei/gwei/ether suffixes)
+        #[arg(long)]
+        gas_price: Option<String>,
+
+        /// Build the transaction without contacting the RPC (requires --chain-id, --nonce, --gas-limit and explicit fees)
+        #[arg(long, conflicts_with_all = ["interactive", "fee_strategy", "gas_multiplier"], requires_all = ["chain_id", "nonce"])]
+        offline: bool,
+
+        /// Chain ID to sign for (offline mode only)
+        #[arg(long, requires = "offline")]
+        chain_id: Option<u64>,
+
+        /// Account nonce of --from, or of the first transaction of a bundle (offline mode only)
+        #[arg(long, requires = "offline")]
+        nonce: Option<u64>,
+
+        /// Write the unsigned transaction even if the pre-flight simulation reverts (requires --gas-limit)
+        #[arg(long)]
+        force: bool,
+
+        /// Attach an EIP-2930 access list generated with eth_createAccessList
+        #[arg(long, conflicts_with = "offline")]
+        access_list: bool,
+
+        /// Blob data file for an EIP-4844 blob transaction (repeat for several files; requires --to)
+        #[arg(long = "blob", requires = "to", conflicts_with_all = ["interactive", "contract", "gas_price"])]
+        blobs: Vec<String>,
+
+        /// How blob files are turned into blobs
+        #[arg(long, value_enum, default_value_t = BlobEncoding::Packed)]
+        blob_encoding: BlobEncoding,
+
+        /// KZG proofs carried with the blobs: "cell" (EIP-7594, Osaka and later) or "blob" (original EIP-4844)
+        #[arg(long, value_enum, default_value_t = BlobProofs::Cell)]
+        blob_proofs: BlobProofs,
+
+        /// Max fee per blob gas in gwei (default: 2x the current blob base fee; accepts wei/gwei/ether suffixes)
+        #[arg(long, requires = "blobs")]
+        max_blob_fee: Option<String>,
+
+        /// Delegate the sender's code to this contract with an EIP-7702 authorization (0x0 clears a delegation)
+        #[arg(long, requires = "to", conflicts_with_all = ["interactive", "blobs", "gas_price"])]
+        delegate: Option<String>,
+
+        /// Deploy with CREATE2 through a factory, at an address fixed by the salt and init code
+        #[arg(long, requires_all = ["contract", "salt"], conflicts_with_all = ["interactive", "to"])]
+        create2: bool,
+
+        /// CREATE2 salt (32 bytes of hex)
+        #[arg(long, requires = "create2")]
+        salt: Option<String>,
+
+        /// CREATE2 factory (default: the deterministic deployment proxy 0x4e59b44847b379578588920cA78FbF26c0B4956C)
+        #[arg(long, requires = "create2")]
+        factory: Option<String>,
+
+        /// Library address for linking, as Name=0xAddress or path/File.sol:Name=0xAddress (repeat per library)
+        #[arg(long = "link", value_name = "LIBRARY=ADDRESS", requires = "contract", conflicts_with = "interactive")]
+        links: Vec<String>,
+
+        /// Import the transactions of a `forge script` broadcast file (broadcast/.../run-latest.json) as a bundle
+        #[arg(long, value_name = "FILE", conflicts_with_all = [
+            "interactive", "contract", "to", "sig", "data", "args", "arg", "args_file", "gas_limit",
+            "offline", "force", "access_list", "blobs", "delegate", "create2", "links",
+        ])]
+        from_forge: Option<String>,
+
+        /// Prepare an ordered bundle of transactions with consecutive nonces from a JSON spec file
+        #[arg(long, value_name = "FILE", conflicts_with_all = [
+            "interactive", "contract", "contract_name", "to", "function_name", "sig", "data", "args", "arg",
+            "args_file", "value", "gas_limit", "blobs", "delegate", "create2", "links", "from_forge",
+        ])]
+        bundle: Option<String>,
+
+        /// Batch the calls listed in a JSON file into one Multicall3 call (--to overrides the Multicall3 address)
+        #[arg(long, value_name = "FILE", conflicts_with_all = [
+            "interactive", "contract", "function_name", "sig", "data", "args", "arg", "args_file", "value",
+            "blobs", "delegate", "create2", "links", "from_forge", "bundle",
+        ])]
+        multicall: Option<String>,
     },
 
     /// Sign the unsigned transaction with encrypted keystore
@@ -99,6 +224,34 @@ enum Commands {
         /// Output file path for signed transaction
         #[arg(short, long, default_value = "signed.json")]
         output: String,
+
+        /// Contract artifact or ABI JSON used to decode the calldata for review
+        #[arg(long)]
+        abi: Option<String>,
+
+        /// Sign without asking for confirmation (for scripted use only)
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// Decode calldata offline, with an ABI or the bundled signature database
+    Decode {
+        /// Calldata as hex (selector followed by the encoded arguments)
+        calldata: String,
+
+        /// Contract artifact or ABI JSON to decode with (default: the bundled signature database)
+        #[arg(long)]
+        abi: Option<String>,
+    },
+
+    /// Decode an unsigned, signed or raw transaction and check it is consistent
+    Inspect {
+        /// unsigned.json or signed.json (single or bundle), or a raw signed transaction as 0x-prefixed hex
+        input: String,
+
+        /// Contract artifact or ABI JSON to decode the calldata with (default: the bundled signature database)
+        #[arg(long)]
+        abi: Option<String>,
     },
 
     /// Broadcast signed transaction to the network (uses RPC URL from signed.json)
@@ -106,6 +259,18 @@ enum Commands {
         /// Path to signed transaction JSON
         #[arg(short, long)]
         signed: String,
+
+        /// RPC endpoint URL (overrides the one stored in signed.json)
+        #[arg(short, long, conflicts_with_all = ["network", "infura_key"])]
+        rpc_url: Option<String>,
+
+        /// Network name for Infura (overrides the RPC URL stored in signed.json)
+        #[arg(short, long, requires = "infura_key")]
+        network: Option<String>,
+
+        /// Infura API key (required when using --network)
+        #[arg(short, long, requires = "network")]
+        infura_key: Option<String>,
     },
 }
 
@@ -117,21 +282,50 @@ async fn main() -> Result<()> {
         Commands::Prepare {
             interactive,
             contract,
+            contract_name,
             rpc_url,
             network,
             infura_key,
             from,
             to,
             function_name,
+            sig,
+            data,
             args,
+            arg,
+            args_file,
             value,
             output,
             gas_limit,
+            gas_multiplier,
+            fee_strategy,
+            max_fee,
+            priority_fee,
+            gas_price,
+            offline,
+            chain_id,
+            nonce,
+            force,
+            access_list,
+            blobs,
+            blob_encoding,
+            blob_proofs,
+            max_blob_fee,
+            delegate,
+            create2,
+            salt,
+            factory,
+            links,
+            from_forge,
+            bundle,
+            multicall,
         } => {
+            let args = utils::abi_args::collect_cli_args(args, arg, args_file)?;
             if interactive {
                 // Interactive web UI mode
-                commands::prepare_interactive::execute(
+                commands::prepare_interactive::execute(DefaultParams {
                     contract,
+                    contract_name,
                     rpc_url,
                     network,
                     infura_key,
@@ -142,36 +336,120 @@ async fn main() -> Result<()> {
                     value,
                     output,
                     gas_limit,
-                )
+                    gas_multiplier,
+                    fee_strategy,
+                    max_fee,
+                    priority_fee,
+                    gas_price,
+                    force,
+                    access_list,
+                })
                 .await?;
-            } else {
-                // CLI mode - contract and from are required (enforced by clap)
-                let contract = contract.expect("contract is required in CLI mode");
-                let from = from.expect("from is required in CLI mode");
-                let resolved_rpc_url = utils::rpc::resolve_rpc_url(rpc_url, network, infura_key)?;
-                commands::prepare::execute(
-                    contract,
-                    resolved_rpc_url,
+            } else if let Some(path) = from_forge {
+                commands::prepare_forge::execute(ForgeImportParams {
+                    path,
+                    rpc_url: utils::rpc::resolve_rpc_url(rpc_url, network, infura_key)?,
                     from,
-                    to,
-                    function_name,
-                    args,
-                    value,
                     output,
-                    gas_limit,
-                )
+                    fee_strategy,
+                    max_fee,
+                    priority_fee,
+                    gas_price,
+                })
                 .await?;
+            } else {
+                // CLI mode - from is required (enforced by clap)
+                let from = from.expect("from is required in CLI mode");
+                // Offline mode may still record an RPC URL for broadcast, but does not need one
+                let resolved_rpc_url = if offline && rpc_url.is_none() && network.is_none() {
+                    None
+                } else {
+                    Some(utils::rpc::resolve_rpc_url(rpc_url, network, infura_key)?)
+                };
+                if let Some(path) = bundle {
+                    commands::prepare_bundle::execute(BundleParams {
+                        path,
+                        rpc_url: resolved_rpc_url,
+                        from,
+                        output,
+                        gas_multiplier,
+                        fee_strategy,
+                        max_fee,
+                        priority_fee,
+                        gas_price,
+                        offline,
+                        chain_id,
+                        nonce,
+                        force,
+                        access_list,
+                    })
+                    .await?;
+                } else {
+                    commands::prepare::execute(PrepareParams {
+                        contract,
+                        contract_name,
+                        rpc_url: resolved_rpc_url,
+                        from,
+                        to,
+                        function_name,
+                        sig,
+                        data,
+                        args,
+                        value,
+                        output,
+                        gas_limit,
+                        gas_multiplier,
+                        fee_strategy,
+                        max_fee,
+                        priority_fee,
+                        gas_price,
+                        offline,
+                        chain_id,
+                        nonce,
+                        force,
+                        access_list,
+                        blobs,
+                        blob_encoding,
+                        blob_proofs,
+                        max_blob_fee,
+                        delegate,
+                        create2,
+                        salt,
+                        factory,
+                        links,
+                        multicall,
+                    })
+                    .await?;
+                }
             }
         }
         Commands::Sign {
             unsigned,
             keystore,
             output,
+            abi,
+            yes,
         } => {
-            commands::sign::execute(unsigned, keystore, output).await?;
+            commands::sign::execute(unsigned, keystore, output, abi, yes).await?;
+        }
+        Commands::Broadcast {
+            signed,
+            rpc_url,
+            network,
+            infura_key,
+        } => {
+            let rpc_override = if rpc_url.is_some() || network.is_some() {
+                Some(utils::rpc::resolve_rpc_url(rpc_url, network, infura_key)?)
+            } else {
+                None
+            };
+            commands::broadcast::execute(signed, rpc_override).await?;
+        }
+        Commands::Decode { calldata, abi } => {
+            commands::decode::execute(calldata, abi).await?;
         }
-        Commands::Broadcast { signed } => {
-            commands::broadcast::execute(signed).await?;
+        Commands::Inspect { input, abi } => {
+            commands::inspect::execute(input, abi).await?;
         }
         Commands::GenerateMnemonic => {
             commands::generate_mnemonic::execute().await?;
@@ -1,2 +1,3 @@
 pub mod prepare_output;
 pub mod sign_output;
+pub mod u256_string;
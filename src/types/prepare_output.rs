@@ -1,15 +1,142 @@
+use ethers::types::{transaction::eip2930::AccessList, Bytes, H256, U256};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnsignedTransaction {
+    /// Sender the nonce was assigned for; the signing keystore must be this account.
+    /// Absent from files written before it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
     pub to: Option<String>,
     pub data: String,
     pub nonce: u64,
     pub gas_limit: u64,
-    pub gas_price: Option<u64>,
-    pub max_fee_per_gas: Option<u64>,
-    pub max_priority_fee_per_gas: Option<u64>,
+    /// Whether `gas_limit` came from `eth_estimateGas` or from `--gas-limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit_source: Option<GasLimitSource>,
+    #[serde(with = "super::u256_string::option")]
+    pub gas_price: Option<U256>,
+    #[serde(with = "super::u256_string::option")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(with = "super::u256_string::option")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// EIP-2930 access list; signed as a type-1 (legacy fees) or type-2 transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+    /// EIP-4844 max fee per blob gas (type-3 transactions only)
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "super::u256_string::option")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// EIP-4844 versioned hashes of the blob commitments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+    /// Blobs with their KZG commitments and proofs, sent alongside the signed transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_sidecar: Option<BlobSidecar>,
+    /// EIP-7702 authorizations, signed by the keystore account together with the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorization_list: Option<Vec<Authorization>>,
+    /// CREATE2 deployment routed through a factory; `to` is the factory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create2: Option<Create2Deployment>,
+    /// Calls batched through Multicall3; `to` is Multicall3 and `data` its `aggregate3` or
+    /// `aggregate3Value` call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multicall: Option<Vec<MulticallCall>>,
+    /// Function or constructor that `data` encodes, with its arguments, so the signer can
+    /// review the call without the artifact
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<CallMetadata>,
+    /// Address of the deployed contract, predicted from the sender and nonce (CREATE)
+    /// or from the factory, salt and init code (CREATE2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
     pub chain_id: u64,
-    pub value: String,
-    pub rpc_url: String,
+    /// Amount in wei
+    #[serde(with = "super::u256_string")]
+    pub value: U256,
+    /// RPC endpoint for broadcast; absent for transactions prepared offline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+}
+
+/// Ordered transactions from one sender, signed with one password prompt and broadcast
+/// in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedBundle {
+    pub chain_id: u64,
+    /// RPC endpoint for broadcast
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    pub transactions: Vec<UnsignedTransaction>,
+}
+
+/// Blob sidecar of an EIP-4844 transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobSidecar {
+    /// Network wrapper version: 0 for per-blob proofs (EIP-4844), 1 for cell proofs (EIP-7594)
+    pub wrapper_version: u8,
+    pub blobs: Vec<Bytes>,
+    pub commitments: Vec<Bytes>,
+    pub proofs: Vec<Bytes>,
+}
+
+/// Unsigned EIP-7702 authorization: delegate the signing account's code to `address`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Authorization {
+    pub chain_id: u64,
+    /// Contract whose code the account will run; the zero address clears a delegation
+    pub address: String,
+    pub nonce: u64,
+}
+
+/// Deterministic deployment through a CREATE2 factory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Create2Deployment {
+    pub factory: String,
+    pub salt: H256,
+}
+
+/// Decoding metadata for `data`; the signer re-encodes it and refuses a file where it
+/// does not match, so it cannot misdescribe the calldata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CallMetadata {
+    /// ABI entry of the called function or of the constructor
+    pub abi: serde_json::Value,
+    /// Arguments in the JSON form accepted by `--args`, one per input
+    pub args: Vec<serde_json::Value>,
+    /// Artifact the ABI was read from; absent when it came from `--sig`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<ArtifactSource>,
+}
+
+/// Compiled contract file that a transaction was prepared from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactSource {
+    pub path: String,
+    /// SHA-256 of the file, hex
+    pub sha256: String,
+}
+
+/// One call made by Multicall3 on behalf of the sender
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MulticallCall {
+    pub target: String,
+    /// Amount in wei forwarded with the call
+    #[serde(with = "super::u256_string")]
+    pub value: U256,
+    /// Whether the batch continues when this call reverts
+    pub allow_failure: bool,
+    pub call_data: String,
+    /// Signature of the called function, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GasLimitSource {
+    Estimated,
+    User,
+    /// Taken from a `forge script` broadcast file, which forge simulated
+    Forge,
 }
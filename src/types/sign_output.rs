@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedTransaction {
     pub raw_transaction: String,
     pub transaction_hash: String,
@@ -8,5 +8,21 @@ pub struct SignedTransaction {
     pub to: Option<String>,
     pub nonce: u64,
     pub chain_id: u64,
-    pub rpc_url: String,
+    /// Predicted address of the deployed contract, checked against the receipt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+    /// RPC endpoint for broadcast; absent for transactions prepared offline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+}
+
+/// Signed counterpart of an `UnsignedBundle`, broadcast in order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignedBundle {
+    pub chain_id: u64,
+    pub from: String,
+    /// RPC endpoint for broadcast; absent for transactions prepared offline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    pub transactions: Vec<SignedTransaction>,
 }
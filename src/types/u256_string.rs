@@ -0,0 +1,59 @@
+//! Serde helpers that write `U256` amounts as decimal strings and read them back from
+//! decimal strings, `0x`-prefixed hex strings or plain JSON integers.
+
+use ethers::types::U256;
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Raw {
+    String(String),
+    Number(u64),
+}
+
+fn parse(raw: Raw) -> Result<U256, String> {
+    match raw {
+        Raw::Number(n) => Ok(U256::from(n)),
+        Raw::String(s) => {
+            let trimmed = s.trim();
+            let parsed = match trimmed
+                .strip_prefix("0x")
+                .or_else(|| trimmed.strip_prefix("0X"))
+            {
+                Some(hex) if !hex.is_empty() && hex.len() <= 64 => U256::from_str_radix(hex, 16).ok(),
+                Some(_) => None,
+                None if !trimmed.is_empty() && trimmed.chars().all(|c| c.is_ascii_digit()) => {
+                    U256::from_dec_str(trimmed).ok()
+                }
+                None => None,
+            };
+            parsed.ok_or_else(|| format!("invalid U256 amount '{}': expected a decimal or 0x-prefixed hex string", s))
+        }
+    }
+}
+
+pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    parse(Raw::deserialize(deserializer)?).map_err(de::Error::custom)
+}
+
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<U256>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(v) => serializer.serialize_some(&v.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<U256>, D::Error> {
+        Option::<Raw>::deserialize(deserializer)?
+            .map(parse)
+            .transpose()
+            .map_err(de::Error::custom)
+    }
+}
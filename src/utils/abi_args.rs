@@ -0,0 +1,666 @@
+use anyhow::{Context, Result};
+use ethers::{
+    abi::{Function, HumanReadableParser, Param, ParamType, Token},
+    types::{H160, I256, U256},
+    utils::to_checksum,
+};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// Parse a comma-separated argument list written in Solidity literal syntax.
+///
+/// Commas only separate arguments at the top level, so nested arrays, tuples and
+/// quoted strings may contain commas of their own:
+///
+/// `0xAbC..., [1, 2, 3], ("name, with comma", -5), 1.5e18`
+///
+/// Each argument is returned as a JSON value (strings for scalars, arrays for
+/// `[...]` and `(...)` groups) and is later converted with [`value_to_token`].
+pub fn parse_arg_list(input: &str) -> Result<Vec<Value>> {
+    let mut parser = LiteralParser::new(input);
+    parser.skip_whitespace();
+    if parser.at_end() {
+        return Ok(vec![]);
+    }
+
+    let values = parser.parse_sequence(None)?;
+    parser.skip_whitespace();
+    if !parser.at_end() {
+        anyhow::bail!(
+            "Unexpected '{}' at position {} in argument list",
+            parser.peek().unwrap(),
+            parser.pos
+        );
+    }
+    Ok(values)
+}
+
+/// Parse an argument string that is either a JSON array (`["0xabc...", [1, 2]]`)
+/// or a Solidity literal list (see [`parse_arg_list`]).
+///
+/// Input starting with `[` that is valid JSON is always taken as a JSON array of
+/// arguments; a single array argument in literal syntax can be passed with `--arg`.
+pub fn parse_args_input(input: &str) -> Result<Vec<Value>> {
+    if input.trim_start().starts_with('[') {
+        if let Ok(Value::Array(values)) = serde_json::from_str::<Value>(input) {
+            return Ok(values);
+        }
+    }
+    parse_arg_list(input)
+}
+
+/// Collect arguments from the mutually exclusive `--args`, `--arg` and `--args-file` flags.
+///
+/// Each repeated `--arg` is taken verbatim as one argument, so commas inside it never
+/// split it further; array and tuple values are still read as Solidity literals.
+pub fn collect_cli_args(
+    args: Option<String>,
+    arg: Vec<String>,
+    args_file: Option<String>,
+) -> Result<Vec<Value>> {
+    if let Some(path) = args_file {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read arguments file: {}", path))?;
+        return parse_args_input(&content)
+            .with_context(|| format!("Failed to parse arguments file: {}", path));
+    }
+    if let Some(args) = args {
+        return parse_args_input(&args).context("Failed to parse --args");
+    }
+    Ok(arg.into_iter().map(Value::String).collect())
+}
+
+/// Parse a human-readable function signature such as `transfer(address,uint256)` or
+/// `function deposit((address token, uint256 amount)[] items) payable`
+pub fn parse_signature(signature: &str) -> Result<Function> {
+    let function = HumanReadableParser::parse_function(signature.trim())
+        .map_err(|e| anyhow::anyhow!("Invalid function signature '{}': {}", signature, e))?;
+    if function.name.is_empty() {
+        anyhow::bail!("Function signature '{}' has no name", signature);
+    }
+    Ok(function)
+}
+
+/// Convert a list of argument values into tokens for the given ABI inputs.
+///
+/// `target` names the constructor or function in error messages, which also point
+/// at the offending argument index and parameter name.
+pub fn tokenize_args(target: &str, args: &[Value], inputs: &[Param]) -> Result<Vec<Token>> {
+    if args.is_empty() && !inputs.is_empty() {
+        let param_list: Vec<String> = inputs
+            .iter()
+            .map(|p| format!("{}: {}", p.name, p.kind))
+            .collect();
+        anyhow::bail!(
+            "{} requires {} parameter(s) but none were provided: {}\n\
+             Use --args, --arg or --args-file to supply arguments.",
+            target,
+            inputs.len(),
+            param_list.join(", ")
+        );
+    }
+    if args.len() != inputs.len() {
+        anyhow::bail!(
+            "{} expects {} argument(s) but {} were provided",
+            target,
+            inputs.len(),
+            args.len()
+        );
+    }
+
+    args.iter()
+        .zip(inputs.iter())
+        .enumerate()
+        .map(|(i, (arg, param))| {
+            value_to_token(arg, &param.kind).with_context(|| {
+                let name = if param.name.is_empty() { "<unnamed>" } else { &param.name };
+                format!(
+                    "{}: invalid argument [{}] '{}' ({})",
+                    target, i, name, param.kind
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parse a single value written in Solidity literal syntax (see [`parse_arg_list`])
+pub fn parse_literal(input: &str) -> Result<Value> {
+    let mut parser = LiteralParser::new(input);
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if !parser.at_end() {
+        anyhow::bail!(
+            "Unexpected '{}' at position {} in value '{}'",
+            parser.peek().unwrap(),
+            parser.pos,
+            input
+        );
+    }
+    Ok(value)
+}
+
+/// Convert a JSON or Solidity-literal value into an ABI token of the given type.
+///
+/// Composite types (arrays and tuples) accept either a JSON array or a string
+/// holding a Solidity literal such as `"[1, 2, 3]"`.
+pub fn value_to_token(value: &Value, kind: &ParamType) -> Result<Token> {
+    match kind {
+        ParamType::Array(inner) => {
+            let items = expect_list(value, kind)?;
+            let tokens = items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    value_to_token(item, inner).with_context(|| format!("element [{}]", i))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Token::Array(tokens))
+        }
+        ParamType::FixedArray(inner, len) => {
+            let items = expect_list(value, kind)?;
+            if items.len() != *len {
+                anyhow::bail!(
+                    "{} expects exactly {} element(s) but {} were provided",
+                    kind,
+                    len,
+                    items.len()
+                );
+            }
+            let tokens = items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    value_to_token(item, inner).with_context(|| format!("element [{}]", i))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Token::FixedArray(tokens))
+        }
+        ParamType::Tuple(components) => {
+            let items = expect_list(value, kind)?;
+            if items.len() != components.len() {
+                anyhow::bail!(
+                    "{} expects {} component(s) but {} were provided",
+                    kind,
+                    components.len(),
+                    items.len()
+                );
+            }
+            let tokens = items
+                .iter()
+                .zip(components.iter())
+                .enumerate()
+                .map(|(i, (item, component))| {
+                    value_to_token(item, component).with_context(|| format!("component {}", i))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Token::Tuple(tokens))
+        }
+        _ => {
+            let scalar = scalar_string(value, kind)?;
+            parse_scalar(&scalar, kind)
+        }
+    }
+}
+
+/// Format a decoded ABI token in the Solidity literal syntax accepted by [`parse_literal`]
+pub fn format_token(token: &Token) -> String {
+    match token {
+        Token::Address(address) => to_checksum(address, None),
+        Token::Uint(value) => value.to_string(),
+        Token::Int(value) => I256::from_raw(*value).to_string(),
+        Token::Bool(value) => value.to_string(),
+        Token::String(value) => quote_string(value),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Array(items) | Token::FixedArray(items) => format!("[{}]", format_tokens(items)),
+        Token::Tuple(items) => format!("({})", format_tokens(items)),
+    }
+}
+
+/// Convert a decoded ABI token back into the JSON argument form read by [`value_to_token`]
+pub fn token_to_value(token: &Token) -> Value {
+    match token {
+        Token::Array(items) | Token::FixedArray(items) | Token::Tuple(items) => {
+            Value::Array(items.iter().map(token_to_value).collect())
+        }
+        Token::String(value) => Value::String(value.clone()),
+        other => Value::String(format_token(other)),
+    }
+}
+
+fn format_tokens(tokens: &[Token]) -> String {
+    tokens.iter().map(format_token).collect::<Vec<_>>().join(", ")
+}
+
+/// Double-quote a string with only the escapes the literal parser reads back
+fn quote_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Parse an unsigned integer in decimal, hex (`0x`) or scientific (`1.5e18`) notation.
+/// Underscores may be used as digit separators.
+pub fn parse_uint(input: &str) -> Result<U256> {
+    let cleaned: String = input.trim().chars().filter(|c| *c != '_').collect();
+    if cleaned.is_empty() {
+        anyhow::bail!("Empty number");
+    }
+
+    if let Some(hex_digits) = cleaned
+        .strip_prefix("0x")
+        .or_else(|| cleaned.strip_prefix("0X"))
+    {
+        if hex_digits.is_empty() || hex_digits.len() > 64 {
+            anyhow::bail!("Invalid hex number: '{}'", input);
+        }
+        return U256::from_str_radix(hex_digits, 16)
+            .map_err(|_| anyhow::anyhow!("Invalid hex number: '{}'", input));
+    }
+
+    let (mantissa, exponent) = match cleaned.find(['e', 'E']) {
+        Some(idx) => {
+            let exp: u32 = cleaned[idx + 1..]
+                .strip_prefix('+')
+                .unwrap_or(&cleaned[idx + 1..])
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid exponent in number: '{}'", input))?;
+            (&cleaned[..idx], exp)
+        }
+        None => (cleaned.as_str(), 0),
+    };
+
+    let (int_part, frac_part) = match mantissa.split_once('.') {
+        Some((i, f)) => (i, f.trim_end_matches('0')),
+        None => (mantissa, ""),
+    };
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.chars().all(|c| c.is_ascii_digit())
+        || !frac_part.chars().all(|c| c.is_ascii_digit())
+    {
+        anyhow::bail!("Invalid number: '{}'", input);
+    }
+
+    let frac_len = frac_part.len() as u32;
+    if frac_len > exponent {
+        anyhow::bail!("Number '{}' is not an integer", input);
+    }
+
+    let digits = format!("{}{}", int_part, frac_part);
+    let digits = digits.trim_start_matches('0');
+    let base = if digits.is_empty() {
+        U256::zero()
+    } else {
+        U256::from_dec_str(digits)
+            .map_err(|_| anyhow::anyhow!("Number '{}' does not fit in 256 bits", input))?
+    };
+
+    let scale = U256::from(10u64)
+        .checked_pow(U256::from(exponent - frac_len))
+        .ok_or_else(|| anyhow::anyhow!("Number '{}' does not fit in 256 bits", input))?;
+    base.checked_mul(scale)
+        .ok_or_else(|| anyhow::anyhow!("Number '{}' does not fit in 256 bits", input))
+}
+
+/// Parse a signed integer into its magnitude and sign (`true` when negative)
+pub fn parse_int(input: &str) -> Result<(U256, bool)> {
+    let trimmed = input.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let magnitude = parse_uint(digits)?;
+    Ok((magnitude, negative && !magnitude.is_zero()))
+}
+
+fn parse_scalar(input: &str, kind: &ParamType) -> Result<Token> {
+    match kind {
+        ParamType::Address => Ok(Token::Address(parse_address(input)?)),
+        ParamType::Uint(bits) => {
+            let value = parse_uint(input)?;
+            if *bits < 256 && !(value >> *bits).is_zero() {
+                anyhow::bail!("Value {} is out of range for uint{}", input, bits);
+            }
+            Ok(Token::Uint(value))
+        }
+        ParamType::Int(bits) => {
+            let (magnitude, negative) = parse_int(input)?;
+            // intN covers [-2^(N-1), 2^(N-1) - 1]
+            let limit = U256::one() << (*bits - 1);
+            let in_range = if negative {
+                magnitude <= limit
+            } else {
+                magnitude < limit
+            };
+            if !in_range {
+                anyhow::bail!("Value {} is out of range for int{}", input, bits);
+            }
+            let raw = if negative {
+                (!magnitude).overflowing_add(U256::one()).0
+            } else {
+                magnitude
+            };
+            Ok(Token::Int(raw))
+        }
+        ParamType::Bool => match input.trim().to_lowercase().as_str() {
+            "true" => Ok(Token::Bool(true)),
+            "false" => Ok(Token::Bool(false)),
+            _ => anyhow::bail!("Invalid bool '{}': expected true or false", input),
+        },
+        ParamType::String => Ok(Token::String(input.to_string())),
+        ParamType::Bytes => Ok(Token::Bytes(parse_hex_bytes(input)?)),
+        ParamType::FixedBytes(len) => {
+            let bytes = parse_hex_bytes(input)?;
+            if bytes.len() != *len {
+                anyhow::bail!(
+                    "bytes{} expects exactly {} byte(s) but '{}' is {} byte(s)",
+                    len,
+                    len,
+                    input,
+                    bytes.len()
+                );
+            }
+            Ok(Token::FixedBytes(bytes))
+        }
+        _ => anyhow::bail!("Unsupported parameter type: {}", kind),
+    }
+}
+
+/// Parse an address, enforcing the EIP-55 checksum when the input is mixed-case
+fn parse_address(input: &str) -> Result<H160> {
+    let trimmed = input.trim();
+    let addr = H160::from_str(trimmed)
+        .map_err(|_| anyhow::anyhow!("Invalid address: '{}'", input))?;
+
+    let hex_part = trimmed.strip_prefix("0x").unwrap_or(trimmed);
+    let has_lower = hex_part.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex_part.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        let checksummed = ethers::utils::to_checksum(&addr, None);
+        if checksummed[2..] != *hex_part {
+            anyhow::bail!(
+                "Address '{}' has an invalid checksum (expected {})",
+                input,
+                checksummed
+            );
+        }
+    }
+    Ok(addr)
+}
+
+fn parse_hex_bytes(input: &str) -> Result<Vec<u8>> {
+    let trimmed = input.trim();
+    let hex_part = trimmed.strip_prefix("0x").unwrap_or(trimmed);
+    hex::decode(hex_part).with_context(|| format!("Invalid hex bytes: '{}'", input))
+}
+
+fn scalar_string(value: &Value, kind: &ParamType) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                Ok(u.to_string())
+            } else if let Some(i) = n.as_i64() {
+                Ok(i.to_string())
+            } else {
+                anyhow::bail!(
+                    "Number {} cannot be represented exactly; pass it as a string (e.g. \"{}\")",
+                    n,
+                    n
+                )
+            }
+        }
+        _ => anyhow::bail!("Expected a {} value but got {}", kind, value),
+    }
+}
+
+fn expect_list(value: &Value, kind: &ParamType) -> Result<Vec<Value>> {
+    match value {
+        Value::Array(items) => Ok(items.clone()),
+        Value::String(s) => match parse_literal(s)? {
+            Value::Array(items) => Ok(items),
+            _ => anyhow::bail!("Expected a {} value in [...] or (...) form but got '{}'", kind, s),
+        },
+        _ => anyhow::bail!("Expected a {} value but got {}", kind, value),
+    }
+}
+
+struct LiteralParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> LiteralParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    /// Parse comma-separated values until `close` (or end of input when `None`)
+    fn parse_sequence(&mut self, close: Option<char>) -> Result<Vec<Value>> {
+        let mut values = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if close.is_some() && self.peek() == close {
+                // Allows both empty groups and a trailing comma
+                return Ok(values);
+            }
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                c if c == close => return Ok(values),
+                Some(c) => anyhow::bail!("Unexpected '{}' at position {}", c, self.pos),
+                None => anyhow::bail!("Missing closing '{}'", close.unwrap()),
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(open @ ('[' | '(')) => {
+                self.bump();
+                let close = if open == '[' { ']' } else { ')' };
+                let values = self.parse_sequence(Some(close))?;
+                self.bump();
+                Ok(Value::Array(values))
+            }
+            Some(quote @ ('"' | '\'')) => {
+                self.bump();
+                self.parse_quoted(quote).map(Value::String)
+            }
+            _ => {
+                let start = self.pos;
+                while let Some(c) = self.peek() {
+                    if matches!(c, ',' | '[' | ']' | '(' | ')') {
+                        break;
+                    }
+                    self.bump();
+                }
+                let token = self.input[start..self.pos].trim();
+                if token.is_empty() {
+                    anyhow::bail!("Missing value at position {}", start);
+                }
+                Ok(Value::String(token.to_string()))
+            }
+        }
+    }
+
+    fn parse_quoted(&mut self, quote: char) -> Result<String> {
+        let start = self.pos;
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                Some('\\') => match self.bump() {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('r') => out.push('\r'),
+                    Some(c) => out.push(c),
+                    None => break,
+                },
+                Some(c) if c == quote => return Ok(out),
+                Some(c) => out.push(c),
+                None => break,
+            }
+        }
+        anyhow::bail!("Unterminated string starting at position {}", start - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn token(input: &str, kind: &str) -> Result<Token> {
+        value_to_token(&Value::String(input.to_string()), &HumanReadableParser::parse_type(kind).unwrap())
+    }
+
+    #[test]
+    fn splits_literal_list_at_top_level_commas_only() {
+        let values = parse_arg_list(r#"0xabc, [1, 2, 3], ("name, with comma", -5), 1.5e18"#).unwrap();
+        assert_eq!(
+            values,
+            vec![
+                json!("0xabc"),
+                json!(["1", "2", "3"]),
+                json!(["name, with comma", "-5"]),
+                json!("1.5e18"),
+            ]
+        );
+        assert_eq!(parse_arg_list("  ").unwrap(), Vec::<Value>::new());
+        assert_eq!(parse_arg_list("[1, 2,]").unwrap(), vec![json!(["1", "2"])]);
+        assert!(parse_arg_list("[1, 2").is_err());
+        assert!(parse_arg_list("1) 2").is_err());
+        assert!(parse_arg_list(r#""unterminated"#).is_err());
+    }
+
+    #[test]
+    fn json_array_input_is_taken_as_the_argument_list() {
+        assert_eq!(parse_args_input(r#"["0xabc", [1, 2]]"#).unwrap(), vec![json!("0xabc"), json!([1, 2])]);
+        // Not valid JSON, so read as one literal array argument
+        assert_eq!(parse_args_input("[a, b]").unwrap(), vec![json!(["a", "b"])]);
+    }
+
+    #[test]
+    fn parses_decimal_hex_scientific_and_separated_numbers() {
+        assert_eq!(parse_uint("1.5e18").unwrap(), U256::from(1_500_000_000_000_000_000u64));
+        assert_eq!(parse_uint("0x10").unwrap(), U256::from(16));
+        assert_eq!(parse_uint("1_000").unwrap(), U256::from(1000));
+        assert_eq!(parse_uint("2.50E+2").unwrap(), U256::from(250));
+        assert!(parse_uint("1.5").is_err());
+        assert!(parse_uint("1e78").is_err());
+        assert!(parse_uint("0x").is_err());
+        assert!(parse_uint("-1").is_err());
+    }
+
+    #[test]
+    fn checks_uint_range_against_bit_width() {
+        assert_eq!(token("255", "uint8").unwrap(), Token::Uint(U256::from(255)));
+        assert!(token("256", "uint8").is_err());
+        assert_eq!(token(&U256::MAX.to_string(), "uint256").unwrap(), Token::Uint(U256::MAX));
+    }
+
+    #[test]
+    fn encodes_negative_ints_in_twos_complement() {
+        assert_eq!(token("-1", "int256").unwrap(), Token::Int(U256::MAX));
+        assert_eq!(token("-128", "int8").unwrap(), Token::Int(U256::MAX - 127));
+        assert_eq!(token("127", "int8").unwrap(), Token::Int(U256::from(127)));
+        assert_eq!(token("-0", "int8").unwrap(), Token::Int(U256::zero()));
+        assert!(token("128", "int8").is_err());
+        assert!(token("-129", "int8").is_err());
+    }
+
+    #[test]
+    fn checks_fixed_bytes_length_and_address_checksum() {
+        assert!(token("0x01", "bytes32").is_err());
+        assert_eq!(token("0x0102", "bytes2").unwrap(), Token::FixedBytes(vec![1, 2]));
+        assert!(token("0x742D35CC6634c0532925A3b844BC9E7595F0BEb0", "address").is_ok());
+        assert!(token("0x742d35cc6634c0532925a3b844bc9e7595f0beb0", "address").is_ok());
+        assert!(token("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "address").is_err());
+    }
+
+    #[test]
+    fn converts_nested_arrays_and_tuples() {
+        let kind = HumanReadableParser::parse_type("(address,uint256[2],bool)[]").unwrap();
+        let value = json!([["0x742d35cc6634c0532925a3b844bc9e7595f0beb0", "[1, 2]", true]]);
+        let tokens = value_to_token(&value, &kind).unwrap();
+        let address = H160::from_str("0x742d35cc6634c0532925a3b844bc9e7595f0beb0").unwrap();
+        assert_eq!(
+            tokens,
+            Token::Array(vec![Token::Tuple(vec![
+                Token::Address(address),
+                Token::FixedArray(vec![Token::Uint(U256::from(1)), Token::Uint(U256::from(2))]),
+                Token::Bool(true),
+            ])])
+        );
+        assert!(value_to_token(&json!([["0x742d35cc6634c0532925a3b844bc9e7595f0beb0", "[1]", true]]), &kind).is_err());
+    }
+
+    #[test]
+    fn formatted_tokens_parse_back_to_the_same_tokens() {
+        let kind = HumanReadableParser::parse_type("(int16,string,bytes,bytes4[])").unwrap();
+        let token = Token::Tuple(vec![
+            Token::Int(U256::MAX - 41),
+            Token::String("a \"quoted\", string".to_string()),
+            Token::Bytes(vec![0xde, 0xad]),
+            Token::Array(vec![Token::FixedBytes(vec![1, 2, 3, 4])]),
+        ]);
+        assert_eq!(format_token(&token), r#"(-42, "a \"quoted\", string", 0xdead, [0x01020304])"#);
+        assert_eq!(value_to_token(&token_to_value(&token), &kind).unwrap(), token);
+    }
+
+    #[test]
+    fn formatted_strings_parse_back_as_literals() {
+        let kind = HumanReadableParser::parse_type("string").unwrap();
+        for value in ["it's", "tab\there\nnew line\r", "back\\slash \"quote\"", "bell \u{7} é"] {
+            let token = Token::String(value.to_string());
+            let literal = format_token(&token);
+            let parsed = parse_arg_list(&literal).unwrap();
+            assert_eq!(value_to_token(&parsed[0], &kind).unwrap(), token, "{}", literal);
+        }
+        assert_eq!(format_token(&Token::String("it's".to_string())), r#""it's""#);
+    }
+
+    #[test]
+    fn parses_signatures_to_their_selector() {
+        let function = parse_signature("transfer(address,uint256)").unwrap();
+        assert_eq!(function.short_signature(), [0xa9, 0x05, 0x9c, 0xbb]);
+        assert!(parse_signature("(uint256)").is_err());
+    }
+}
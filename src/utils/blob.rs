@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use c_kzg::{Blob, KzgSettings, BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT};
+use ethers::types::{Bytes, H256};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+
+use crate::types::prepare_output::BlobSidecar;
+
+/// Maximum number of blobs a single transaction may carry (EIP-7594)
+pub const MAX_BLOBS_PER_TX: usize = 6;
+
+/// Version byte of KZG versioned hashes
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Data bytes stored per field element in packed encoding; the leading byte stays zero
+/// so every element is below the BLS12-381 field modulus
+const PACKED_BYTES_PER_FIELD_ELEMENT: usize = BYTES_PER_FIELD_ELEMENT - 1;
+
+/// How the contents of a blob file are turned into blobs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BlobEncoding {
+    /// Arbitrary data, 31 bytes per field element, zero-padded to whole blobs
+    #[default]
+    Packed,
+    /// Pre-encoded blobs of exactly 131072 bytes each
+    Raw,
+}
+
+/// Which KZG proofs go into the network wrapper
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BlobProofs {
+    /// 128 cell proofs per blob (EIP-7594, required since the Osaka/Fusaka upgrade)
+    #[default]
+    Cell,
+    /// One proof per blob (original EIP-4844 wrapper, for chains without EIP-7594)
+    Blob,
+}
+
+impl BlobProofs {
+    fn wrapper_version(self) -> u8 {
+        match self {
+            BlobProofs::Blob => 0,
+            BlobProofs::Cell => 1,
+        }
+    }
+}
+
+/// Read blob files and build the sidecar (blobs, KZG commitments and proofs)
+pub fn sidecar_from_files(
+    paths: &[String],
+    encoding: BlobEncoding,
+    proofs: BlobProofs,
+) -> Result<BlobSidecar> {
+    let mut blobs = Vec::new();
+    for path in paths {
+        let data = fs::read(path).with_context(|| format!("Failed to read blob file: {}", path))?;
+        let file_blobs =
+            encode_blobs(&data, encoding).with_context(|| format!("Invalid blob file: {}", path))?;
+        println!("Blob file {}: {} byte(s) in {} blob(s)", path, data.len(), file_blobs.len());
+        blobs.extend(file_blobs);
+    }
+
+    if blobs.is_empty() {
+        anyhow::bail!("No blob data provided");
+    }
+    if blobs.len() > MAX_BLOBS_PER_TX {
+        anyhow::bail!(
+            "{} blobs exceed the limit of {} per transaction",
+            blobs.len(),
+            MAX_BLOBS_PER_TX
+        );
+    }
+
+    build_sidecar(blobs, proofs.wrapper_version())
+}
+
+/// Split data into blobs using the given encoding
+pub fn encode_blobs(data: &[u8], encoding: BlobEncoding) -> Result<Vec<Vec<u8>>> {
+    match encoding {
+        BlobEncoding::Raw => {
+            if data.is_empty() || !data.len().is_multiple_of(BYTES_PER_BLOB) {
+                anyhow::bail!(
+                    "Raw blob data must be a multiple of {} bytes (got {})",
+                    BYTES_PER_BLOB,
+                    data.len()
+                );
+            }
+            Ok(data.chunks(BYTES_PER_BLOB).map(<[u8]>::to_vec).collect())
+        }
+        BlobEncoding::Packed => {
+            if data.is_empty() {
+                anyhow::bail!("Blob data is empty");
+            }
+            let per_blob = PACKED_BYTES_PER_FIELD_ELEMENT * (BYTES_PER_BLOB / BYTES_PER_FIELD_ELEMENT);
+            Ok(data
+                .chunks(per_blob)
+                .map(|chunk| {
+                    let mut blob = vec![0u8; BYTES_PER_BLOB];
+                    for (i, piece) in chunk.chunks(PACKED_BYTES_PER_FIELD_ELEMENT).enumerate() {
+                        let start = i * BYTES_PER_FIELD_ELEMENT + 1;
+                        blob[start..start + piece.len()].copy_from_slice(piece);
+                    }
+                    blob
+                })
+                .collect())
+        }
+    }
+}
+
+/// Compute the KZG commitments and proofs for the blobs
+pub fn build_sidecar(blobs: Vec<Vec<u8>>, wrapper_version: u8) -> Result<BlobSidecar> {
+    let settings = kzg_settings();
+    let mut commitments = Vec::with_capacity(blobs.len());
+    let mut proofs = Vec::new();
+
+    for (i, data) in blobs.iter().enumerate() {
+        let blob = Blob::from_bytes(data).with_context(|| format!("Blob {} has an invalid size", i))?;
+        let commitment = settings
+            .blob_to_kzg_commitment(&blob)
+            .map_err(|e| anyhow::anyhow!("Failed to compute KZG commitment for blob {}: {:?}", i, e))?;
+
+        match wrapper_version {
+            0 => {
+                let proof = settings
+                    .compute_blob_kzg_proof(&blob, &commitment.to_bytes())
+                    .map_err(|e| anyhow::anyhow!("Failed to compute KZG proof for blob {}: {:?}", i, e))?;
+                proofs.push(Bytes::from(proof.to_vec()));
+            }
+            1 => {
+                let (_, cell_proofs) = settings
+                    .compute_cells_and_kzg_proofs(&blob)
+                    .map_err(|e| anyhow::anyhow!("Failed to compute cell proofs for blob {}: {:?}", i, e))?;
+                proofs.extend(cell_proofs.iter().map(|proof| Bytes::from(proof.to_vec())));
+            }
+            other => anyhow::bail!("Unsupported blob wrapper version {}", other),
+        }
+
+        commitments.push(Bytes::from(commitment.to_vec()));
+    }
+
+    Ok(BlobSidecar {
+        wrapper_version,
+        blobs: blobs.into_iter().map(Bytes::from).collect(),
+        commitments,
+        proofs,
+    })
+}
+
+/// Versioned hash of a KZG commitment: 0x01 followed by sha256(commitment)[1..]
+pub fn versioned_hash(commitment: &[u8]) -> H256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    H256::from(hash)
+}
+
+/// Versioned hashes of all commitments in the sidecar
+pub fn versioned_hashes(sidecar: &BlobSidecar) -> Vec<H256> {
+    sidecar.commitments.iter().map(|c| versioned_hash(c)).collect()
+}
+
+/// Recompute the sidecar from its blobs and check it against the stored commitments,
+/// proofs and versioned hashes, so a tampered file cannot change what is signed
+pub fn verify_sidecar(sidecar: &BlobSidecar, hashes: &[H256]) -> Result<()> {
+    let blobs = sidecar.blobs.iter().map(|b| b.to_vec()).collect();
+    let expected = build_sidecar(blobs, sidecar.wrapper_version)?;
+
+    if expected.commitments != sidecar.commitments {
+        anyhow::bail!("Blob commitments do not match the blob data");
+    }
+    if expected.proofs != sidecar.proofs {
+        anyhow::bail!("Blob proofs do not match the blob data");
+    }
+    if versioned_hashes(&expected) != hashes {
+        anyhow::bail!("Blob versioned hashes do not match the blob data");
+    }
+    Ok(())
+}
+
+fn kzg_settings() -> &'static KzgSettings {
+    // Mainnet trusted setup bundled with c-kzg, no precomputed tables
+    c_kzg::ethereum_kzg_settings(0)
+}
@@ -0,0 +1,41 @@
+/// Name and native currency symbol of well-known chains, for display
+pub fn chain_info(chain_id: u64) -> Option<(&'static str, &'static str)> {
+    let info = match chain_id {
+        1 => ("Ethereum Mainnet", "ETH"),
+        5 => ("Goerli", "ETH"),
+        17000 => ("Holesky", "ETH"),
+        560048 => ("Hoodi", "ETH"),
+        11155111 => ("Sepolia", "ETH"),
+        10 => ("OP Mainnet", "ETH"),
+        11155420 => ("OP Sepolia", "ETH"),
+        56 => ("BNB Smart Chain", "BNB"),
+        100 => ("Gnosis", "xDAI"),
+        137 => ("Polygon", "POL"),
+        80002 => ("Polygon Amoy", "POL"),
+        324 => ("zkSync Era", "ETH"),
+        8453 => ("Base", "ETH"),
+        84532 => ("Base Sepolia", "ETH"),
+        42161 => ("Arbitrum One", "ETH"),
+        421614 => ("Arbitrum Sepolia", "ETH"),
+        43114 => ("Avalanche C-Chain", "AVAX"),
+        43113 => ("Avalanche Fuji", "AVAX"),
+        59144 => ("Linea", "ETH"),
+        59141 => ("Linea Sepolia", "ETH"),
+        534352 => ("Scroll", "ETH"),
+        _ => return None,
+    };
+    Some(info)
+}
+
+/// `Name (chain ID)`, or just the chain ID for unknown chains
+pub fn describe(chain_id: u64) -> String {
+    match chain_info(chain_id) {
+        Some((name, _)) => format!("{} ({})", name, chain_id),
+        None => format!("unknown chain ({})", chain_id),
+    }
+}
+
+/// Symbol of the chain's native currency (`ETH` when the chain is unknown)
+pub fn native_symbol(chain_id: u64) -> &'static str {
+    chain_info(chain_id).map(|(_, symbol)| symbol).unwrap_or("ETH")
+}
@@ -1,39 +1,540 @@
 use anyhow::{Context, Result};
+use ethers::{
+    abi::{Abi, Function},
+    types::{H160, H256},
+    utils::{get_create2_address_from_hash, keccak256},
+};
+use serde::Deserialize;
 use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
+use std::str::FromStr;
+
+use super::abi_args;
+
+/// Length of a library placeholder (and of a hex address) in the bytecode string
+const PLACEHOLDER_LEN: usize = 40;
 
 #[derive(serde::Deserialize)]
 struct SolcOutput {
     bytecode: Option<Value>,
     abi: Option<Value>,
+    #[serde(default, rename = "linkReferences")]
+    link_references: Option<LinkReferences>,
+    /// Set by Hardhat and Truffle, absent from Foundry artifacts
+    #[serde(default, rename = "contractName")]
+    contract_name: Option<String>,
+}
+
+/// Location of a library address in the bytecode, in bytes
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinkOffset {
+    pub start: usize,
+    pub length: usize,
 }
 
-pub fn parse_contract_json(path: &str) -> Result<(String, Value)> {
+/// `linkReferences` of a compiler artifact: source file -> library name -> offsets
+pub type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<LinkOffset>>>;
+
+/// Bytecode and ABI read from a compiled contract JSON
+#[derive(Debug, Clone)]
+pub struct ContractArtifact {
+    /// Contract name, when the file records it
+    pub name: Option<String>,
+    /// Hex bytecode without `0x`; may still contain library placeholders
+    pub bytecode: String,
+    pub abi: Value,
+    pub link_references: LinkReferences,
+}
+
+/// Library address given with `--link Name=0xAddress` (or `path/File.sol:Name=0xAddress`)
+#[derive(Debug, Clone)]
+pub struct LibraryLink {
+    pub name: String,
+    pub address: H160,
+}
+
+impl FromStr for LibraryLink {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let (name, address) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid --link '{}': expected LibraryName=0xAddress", spec))?;
+        let address = H160::from_str(address.trim())
+            .with_context(|| format!("Invalid library address in --link '{}'", spec))?;
+        Ok(LibraryLink { name: name.trim().to_string(), address })
+    }
+}
+
+impl LibraryLink {
+    /// Whether this link is for library `name` declared in `file`
+    fn matches(&self, file: &str, name: &str) -> bool {
+        self.name == name || self.name == format!("{}:{}", file, name)
+    }
+
+    /// Whether this link resolves a placeholder found in the bytecode. Current solc
+    /// placeholders hash the fully qualified name; older ones embed it, truncated.
+    fn matches_placeholder(&self, placeholder: &str) -> bool {
+        let hashed = format!("__${}$__", &hex::encode(keccak256(self.name.as_bytes()))[..34]);
+        let truncated: String = self.name.chars().take(PLACEHOLDER_LEN - 4).collect();
+        let legacy = format!("{:_<width$}", format!("__{}", truncated), width = PLACEHOLDER_LEN);
+        placeholder == hashed || placeholder == legacy
+    }
+}
+
+/// Read a contract from compiler output.
+///
+/// Understands single-contract artifacts (Hardhat, Foundry, Truffle, or a plain
+/// `{abi, bytecode}` object) and multi-contract files with a top-level `contracts` map
+/// (`solc --standard-json` and `solc --combined-json`). A multi-contract file needs
+/// `contract_name`, as `Name` or `path/File.sol:Name`, unless it holds one contract.
+pub fn parse_contract_json(path: &str, contract_name: Option<&str>) -> Result<ContractArtifact> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read contract file: {}", path))?;
 
-    let parsed: SolcOutput = serde_json::from_str(&content)
+    let json: Value = serde_json::from_str(&content)
+        .with_context(|| "Failed to parse contract JSON. Expected Solidity compiler output format")?;
+
+    match json.get("contracts").and_then(Value::as_object) {
+        Some(contracts) => parse_multi_contract(contracts, contract_name),
+        None => parse_single_contract(json, contract_name),
+    }
+}
+
+fn parse_single_contract(json: Value, contract_name: Option<&str>) -> Result<ContractArtifact> {
+    let parsed: SolcOutput = serde_json::from_value(json)
         .with_context(|| "Failed to parse contract JSON. Expected Solidity compiler output format")?;
 
+    if let (Some(wanted), Some(name)) = (contract_name, parsed.contract_name.as_deref()) {
+        if short_name(wanted) != name {
+            anyhow::bail!("Contract JSON holds contract '{}', not '{}'", name, wanted);
+        }
+    }
+
     let bytecode_value = parsed.bytecode
         .ok_or_else(|| anyhow::anyhow!("No bytecode found in contract JSON"))?;
+    let (bytecode, nested_link_references) = read_bytecode(&bytecode_value)?;
 
-    // Support both a plain string and an object with an "object" field (e.g. Hardhat artifacts)
-    let bytecode = match &bytecode_value {
-        Value::String(s) => s.clone(),
-        Value::Object(map) => map
-            .get("object")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("bytecode object has no \"object\" string field"))?
-            .to_string(),
-        _ => anyhow::bail!("Unexpected bytecode format in contract JSON"),
+    // Hardhat keeps link references next to the bytecode, Foundry inside it
+    let link_references = match nested_link_references {
+        Some(link_references) => link_references,
+        None => parsed.link_references.unwrap_or_default(),
     };
 
     let abi = parsed.abi
         .ok_or_else(|| anyhow::anyhow!("No ABI found in contract JSON"))?;
 
+    Ok(ContractArtifact { name: parsed.contract_name, bytecode, abi, link_references })
+}
+
+/// Select one contract from a `contracts` map: `file -> name -> contract` for standard
+/// JSON output, `file:name -> contract` for combined JSON output
+fn parse_multi_contract(
+    contracts: &serde_json::Map<String, Value>,
+    contract_name: Option<&str>,
+) -> Result<ContractArtifact> {
+    let mut candidates: Vec<(String, &Value)> = Vec::new();
+    for (key, entry) in contracts {
+        if entry.get("abi").is_some() || entry.get("bin").is_some() {
+            candidates.push((key.clone(), entry));
+        } else if let Some(by_name) = entry.as_object() {
+            for (name, contract) in by_name {
+                candidates.push((format!("{}:{}", key, name), contract));
+            }
+        }
+    }
+
+    let selected: Vec<&(String, &Value)> = match contract_name {
+        Some(wanted) => candidates
+            .iter()
+            .filter(|(name, _)| name == wanted || short_name(name) == wanted)
+            .collect(),
+        None => candidates.iter().collect(),
+    };
+    let (name, contract) = match selected.as_slice() {
+        [selected] => *selected,
+        _ => {
+            let list: Vec<String> = candidates.iter().map(|(name, _)| format!("  {}", name)).collect();
+            match contract_name {
+                Some(wanted) if selected.is_empty() => anyhow::bail!(
+                    "Contract '{}' not found in contract JSON; available contracts:\n{}",
+                    wanted,
+                    list.join("\n")
+                ),
+                Some(wanted) => {
+                    let matches: Vec<String> = selected.iter().map(|(name, _)| format!("  {}", name)).collect();
+                    anyhow::bail!(
+                        "Contract name '{}' is ambiguous; use the full path/File.sol:Name of one of:\n{}",
+                        wanted,
+                        matches.join("\n")
+                    )
+                }
+                None if candidates.is_empty() => anyhow::bail!("No contracts found in contract JSON"),
+                None => anyhow::bail!(
+                    "Contract JSON holds {} contracts; select one with --contract-name:\n{}",
+                    candidates.len(),
+                    list.join("\n")
+                ),
+            }
+        }
+    };
+
+    // Standard JSON nests the bytecode under evm, combined JSON calls it bin
+    let bytecode_value = contract
+        .pointer("/evm/bytecode")
+        .or_else(|| contract.get("bin"))
+        .ok_or_else(|| anyhow::anyhow!("No bytecode found for {} (is evm.bytecode in the outputSelection?)", name))?;
+    let (bytecode, link_references) = read_bytecode(bytecode_value)?;
+
+    // Older solc versions emit the combined JSON ABI as a string
+    let abi = match contract.get("abi") {
+        Some(Value::String(abi)) => serde_json::from_str(abi)
+            .with_context(|| format!("Invalid ABI for {}", name))?,
+        Some(abi) => abi.clone(),
+        None => anyhow::bail!("No ABI found for {}", name),
+    };
+
+    Ok(ContractArtifact {
+        name: Some(name.clone()),
+        bytecode,
+        abi,
+        link_references: link_references.unwrap_or_default(),
+    })
+}
+
+/// Bytecode given as a plain string or as an object with an "object" field (e.g. Foundry
+/// artifacts), returned without `0x`, along with any link references stored beside it
+fn read_bytecode(value: &Value) -> Result<(String, Option<LinkReferences>)> {
+    let (bytecode, link_references) = match value {
+        Value::String(s) => (s.as_str(), None),
+        Value::Object(map) => (
+            map.get("object")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("bytecode object has no \"object\" string field"))?,
+            map.get("linkReferences")
+                .map(|refs| serde_json::from_value(refs.clone()))
+                .transpose()
+                .context("Invalid linkReferences in contract JSON")?,
+        ),
+        _ => anyhow::bail!("Unexpected bytecode format in contract JSON"),
+    };
+
     // Remove 0x prefix if present
-    let bytecode = bytecode.strip_prefix("0x").unwrap_or(&bytecode).to_string();
+    Ok((bytecode.strip_prefix("0x").unwrap_or(bytecode).to_string(), link_references))
+}
+
+/// `Name` from `path/File.sol:Name`
+fn short_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Substitute library addresses for the link placeholders in `bytecode`.
+///
+/// Placeholders listed in the artifact's `linkReferences` are matched by library name.
+/// Artifacts without them are scanned for placeholders, which are matched using the
+/// fully qualified `path/File.sol:Name` form. Fails listing every library left
+/// unresolved, and on links that match nothing, so a typo cannot go unnoticed.
+pub fn link_bytecode(
+    bytecode: &str,
+    link_references: &LinkReferences,
+    links: &[LibraryLink],
+) -> Result<String> {
+    let mut linked = bytecode.to_string();
+    let mut used = vec![false; links.len()];
+    let mut unresolved = BTreeSet::new();
+
+    for (file, libraries) in link_references {
+        for (name, offsets) in libraries {
+            let Some(index) = links.iter().position(|link| link.matches(file, name)) else {
+                unresolved.insert(format!("{}:{}", file, name));
+                continue;
+            };
+            used[index] = true;
+            let address = hex::encode(links[index].address);
+            for offset in offsets {
+                let range = offset.start * 2..(offset.start + offset.length) * 2;
+                if offset.length != 20 || !linked.get(range.clone()).is_some_and(|s| s.starts_with("__")) {
+                    anyhow::bail!("linkReferences for {} do not match the bytecode", name);
+                }
+                linked.replace_range(range, &address);
+            }
+            println!("Linked library {} at {:?}", name, links[index].address);
+        }
+    }
+
+    // Without linkReferences (e.g. plain solc output), find the placeholders themselves
+    let mut search_from = 0;
+    while let Some(found) = linked[search_from..].find("__").filter(|_| link_references.is_empty()) {
+        let start = search_from + found;
+        let placeholder = linked
+            .get(start..start + PLACEHOLDER_LEN)
+            .context("Truncated library placeholder in bytecode")?
+            .to_string();
+        search_from = start + PLACEHOLDER_LEN;
+        match links.iter().position(|link| link.matches_placeholder(&placeholder)) {
+            Some(index) => {
+                used[index] = true;
+                linked.replace_range(start..start + PLACEHOLDER_LEN, &hex::encode(links[index].address));
+                println!("Linked library {} at {:?}", links[index].name, links[index].address);
+            }
+            None => {
+                unresolved.insert(placeholder);
+            }
+        }
+    }
+
+    if !unresolved.is_empty() {
+        let list: Vec<String> = unresolved.iter().map(|name| format!("  {}", name)).collect();
+        anyhow::bail!(
+            "Bytecode needs addresses for these libraries:\n{}\nPass --link <Name>=<address> for each (artifacts without linkReferences need path/File.sol:Name=<address>)",
+            list.join("\n")
+        );
+    }
+    if let Some(index) = used.iter().position(|used| !used) {
+        anyhow::bail!("--link {} does not match any library in the bytecode", links[index].name);
+    }
+
+    Ok(linked)
+}
+
+/// Canonical `name(type1,type2)` signature of a function, without outputs
+pub fn canonical_signature(function: &Function) -> String {
+    let inputs: Vec<String> = function.inputs.iter().map(|p| p.kind.to_string()).collect();
+    format!("{}({})", function.name, inputs.join(","))
+}
+
+/// Find a function in the ABI by name, full signature (`name(type1,type2)`) or 4-byte
+/// selector (`0x12345678`).
+///
+/// Overloaded functions cannot be selected by name alone; the error lists every overload
+/// with its selector so the caller can pick one.
+pub fn find_function<'a>(abi: &'a Abi, spec: &str) -> Result<&'a Function> {
+    let spec = spec.trim();
+
+    let matches: Vec<&Function> = if let Some(hex_selector) = spec.strip_prefix("0x") {
+        let selector = hex::decode(hex_selector)
+            .ok()
+            .filter(|s| s.len() == 4)
+            .ok_or_else(|| anyhow::anyhow!("Invalid function selector '{}': expected 4 bytes of hex", spec))?;
+        abi.functions()
+            .filter(|f| f.short_signature()[..] == selector[..])
+            .collect()
+    } else if spec.contains('(') {
+        let selector = abi_args::parse_signature(spec)?.short_signature();
+        abi.functions()
+            .filter(|f| f.short_signature() == selector)
+            .collect()
+    } else {
+        abi.functions_by_name(spec)
+            .map(|fs| fs.iter().collect())
+            .unwrap_or_default()
+    };
+
+    match matches.as_slice() {
+        [] => anyhow::bail!("Function '{}' not found in ABI", spec),
+        [function] => Ok(function),
+        overloads => {
+            let list: Vec<String> = overloads
+                .iter()
+                .map(|f| format!("  {} (0x{})", canonical_signature(f), hex::encode(f.short_signature())))
+                .collect();
+            anyhow::bail!(
+                "Function '{}' is ambiguous; specify the full signature or selector of one of:\n{}",
+                spec,
+                list.join("\n")
+            )
+        }
+    }
+}
+
+/// Address of the contract `factory` deploys with CREATE2 from `salt` and `init_code`
+pub fn create2_address(factory: H160, salt: H256, init_code: &[u8]) -> H160 {
+    get_create2_address_from_hash(factory, salt.as_bytes(), keccak256(init_code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const MATH: &str = "contracts/Math.sol:Math";
+
+    fn link(spec: &str) -> LibraryLink {
+        spec.parse().unwrap()
+    }
+
+    fn hashed_placeholder(name: &str) -> String {
+        format!("__${}$__", &hex::encode(keccak256(name.as_bytes()))[..34])
+    }
+
+    fn link_references(file: &str, name: &str, starts: &[usize]) -> LinkReferences {
+        let offsets = starts.iter().map(|&start| LinkOffset { start, length: 20 }).collect();
+        BTreeMap::from([(file.to_string(), BTreeMap::from([(name.to_string(), offsets)]))])
+    }
+
+    #[test]
+    fn substitutes_addresses_at_link_reference_offsets() {
+        let placeholder = hashed_placeholder(MATH);
+        let bytecode = format!("6080{}00{}", placeholder, placeholder);
+        let references = link_references("contracts/Math.sol", "Math", &[2, 23]);
+        let address = "1111111111111111111111111111111111111111";
+
+        let by_name = link_bytecode(&bytecode, &references, &[link(&format!("Math=0x{}", address))]).unwrap();
+        assert_eq!(by_name, format!("6080{}00{}", address, address));
+        let qualified = link_bytecode(&bytecode, &references, &[link(&format!("{}=0x{}", MATH, address))]).unwrap();
+        assert_eq!(qualified, by_name);
+
+        // Offsets that do not point at a placeholder mean the artifact is inconsistent
+        let shifted = link_references("contracts/Math.sol", "Math", &[1]);
+        assert!(link_bytecode(&bytecode, &shifted, &[link(&format!("Math=0x{}", address))]).is_err());
+    }
+
+    #[test]
+    fn resolves_hashed_and_legacy_placeholders_without_link_references() {
+        let address = "2222222222222222222222222222222222222222";
+        let links = [link(&format!("{}=0x{}", MATH, address))];
+
+        let hashed = format!("73{}f3", hashed_placeholder(MATH));
+        assert_eq!(link_bytecode(&hashed, &LinkReferences::new(), &links).unwrap(), format!("73{}f3", address));
+
+        let legacy = format!("73{:_<40}f3", format!("__{}", MATH));
+        assert_eq!(legacy.len(), 44);
+        assert_eq!(link_bytecode(&legacy, &LinkReferences::new(), &links).unwrap(), format!("73{}f3", address));
+
+        // Only the fully qualified name identifies a placeholder
+        assert!(link_bytecode(&hashed, &LinkReferences::new(), &[link(&format!("Math=0x{}", address))]).is_err());
+    }
+
+    #[test]
+    fn lists_every_unresolved_library() {
+        let bytecode = format!("{}{}", hashed_placeholder(MATH), hashed_placeholder("contracts/Strings.sol:Strings"));
+        let error = link_bytecode(&bytecode, &LinkReferences::new(), &[]).unwrap_err().to_string();
+        assert!(error.contains(&hashed_placeholder(MATH)));
+        assert!(error.contains(&hashed_placeholder("contracts/Strings.sol:Strings")));
+
+        let references = link_references("contracts/Math.sol", "Math", &[0]);
+        let error = link_bytecode(&hashed_placeholder(MATH), &references, &[]).unwrap_err().to_string();
+        assert!(error.contains(MATH));
+    }
+
+    #[test]
+    fn rejects_links_that_match_no_library() {
+        let address = "0x3333333333333333333333333333333333333333";
+        let links = [link(&format!("{}={}", MATH, address)), link(&format!("Strings={}", address))];
+        let error = link_bytecode(&hashed_placeholder(MATH), &LinkReferences::new(), &links).unwrap_err();
+        assert!(error.to_string().contains("--link Strings"));
+
+        assert!(link_bytecode("6080", &LinkReferences::new(), &links[..1]).is_err());
+        assert_eq!(link_bytecode("6080", &LinkReferences::new(), &[]).unwrap(), "6080");
+    }
+
+    fn multi_contract(json: Value, contract_name: Option<&str>) -> Result<ContractArtifact> {
+        parse_multi_contract(json["contracts"].as_object().unwrap(), contract_name)
+    }
+
+    #[test]
+    fn reads_foundry_and_hardhat_artifacts() {
+        let foundry = json!({
+            "abi": [],
+            "bytecode": {
+                "object": "0x6080",
+                "linkReferences": {"src/Math.sol": {"Math": [{"start": 1, "length": 20}]}}
+            }
+        });
+        let artifact = parse_single_contract(foundry, Some("Token")).unwrap();
+        assert_eq!(artifact.bytecode, "6080");
+        assert_eq!(artifact.name, None);
+        assert_eq!(artifact.link_references["src/Math.sol"]["Math"][0].start, 1);
+
+        let hardhat = json!({
+            "contractName": "Token",
+            "abi": [],
+            "bytecode": "0x6080",
+            "linkReferences": {"contracts/Math.sol": {"Math": [{"start": 3, "length": 20}]}}
+        });
+        let artifact = parse_single_contract(hardhat.clone(), Some("contracts/Token.sol:Token")).unwrap();
+        assert_eq!(artifact.name.as_deref(), Some("Token"));
+        assert_eq!(artifact.link_references["contracts/Math.sol"]["Math"][0].start, 3);
+        assert!(parse_single_contract(hardhat, Some("Vault")).is_err());
+
+        assert!(parse_single_contract(json!({"abi": []}), None).is_err());
+    }
+
+    #[test]
+    fn selects_a_contract_from_standard_json_output() {
+        let contract = |code: &str| json!({"abi": [], "evm": {"bytecode": {"object": code, "linkReferences": {}}}});
+        let output = json!({
+            "contracts": {
+                "src/Token.sol": {"Token": contract("01"), "Math": contract("02")},
+                "src/legacy/Token.sol": {"Token": contract("03")}
+            }
+        });
+
+        let artifact = multi_contract(output.clone(), Some("Math")).unwrap();
+        assert_eq!((artifact.name.as_deref(), artifact.bytecode.as_str()), (Some("src/Token.sol:Math"), "02"));
+        let artifact = multi_contract(output.clone(), Some("src/legacy/Token.sol:Token")).unwrap();
+        assert_eq!(artifact.bytecode, "03");
+
+        let error = multi_contract(output.clone(), Some("Token")).unwrap_err().to_string();
+        assert!(error.contains("ambiguous") && error.contains("src/legacy/Token.sol:Token"));
+        let error = multi_contract(output.clone(), None).unwrap_err().to_string();
+        assert!(error.contains("--contract-name") && error.contains("src/Token.sol:Math"));
+        assert!(multi_contract(output, Some("Vault")).unwrap_err().to_string().contains("not found"));
+    }
+
+    #[test]
+    fn reads_combined_json_output() {
+        let output = json!({
+            "contracts": {
+                "src/Token.sol:Token": {"abi": "[{\"type\":\"function\",\"name\":\"pause\",\"inputs\":[],\"outputs\":[],\"stateMutability\":\"nonpayable\"}]", "bin": "6080"}
+            }
+        });
+        let artifact = multi_contract(output, None).unwrap();
+        assert_eq!(artifact.name.as_deref(), Some("src/Token.sol:Token"));
+        assert_eq!(artifact.bytecode, "6080");
+        assert_eq!(artifact.abi[0]["name"], "pause");
+    }
+
+    fn overloaded_abi() -> Abi {
+        serde_json::from_value(json!([
+            {"type": "function", "name": "safeTransferFrom", "stateMutability": "nonpayable", "outputs": [],
+             "inputs": [{"name": "from", "type": "address"}, {"name": "to", "type": "address"}, {"name": "id", "type": "uint256"}]},
+            {"type": "function", "name": "safeTransferFrom", "stateMutability": "nonpayable", "outputs": [],
+             "inputs": [{"name": "from", "type": "address"}, {"name": "to", "type": "address"}, {"name": "id", "type": "uint256"}, {"name": "data", "type": "bytes"}]},
+            {"type": "function", "name": "pause", "stateMutability": "nonpayable", "outputs": [], "inputs": []}
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_unique_functions_by_name() {
+        let abi = overloaded_abi();
+        assert_eq!(find_function(&abi, " pause ").unwrap().name, "pause");
+        assert!(find_function(&abi, "unpause").unwrap_err().to_string().contains("not found"));
+    }
+
+    #[test]
+    fn selects_overloads_by_signature_or_selector() {
+        let abi = overloaded_abi();
+        let with_data = find_function(&abi, "safeTransferFrom(address,address,uint256,bytes)").unwrap();
+        assert_eq!(with_data.inputs.len(), 4);
+        let named = find_function(&abi, "safeTransferFrom(address from, address to, uint256 id)").unwrap();
+        assert_eq!(named.inputs.len(), 3);
+
+        // safeTransferFrom(address,address,uint256) is 0x42842e0e
+        assert_eq!(find_function(&abi, "0x42842e0e").unwrap().inputs.len(), 3);
+        assert_eq!(find_function(&abi, "0xb88d4fde").unwrap().inputs.len(), 4);
+        assert!(find_function(&abi, "0x42842e").is_err());
+        assert!(find_function(&abi, "0xdeadbeef").is_err());
+        assert!(find_function(&abi, "safeTransferFrom(address,uint256)").is_err());
+    }
 
-    Ok((bytecode, abi))
+    #[test]
+    fn ambiguous_name_lists_every_overload() {
+        let error = find_function(&overloaded_abi(), "safeTransferFrom").unwrap_err().to_string();
+        assert!(error.contains("ambiguous"));
+        assert!(error.contains("safeTransferFrom(address,address,uint256) (0x42842e0e)"));
+        assert!(error.contains("safeTransferFrom(address,address,uint256,bytes) (0xb88d4fde)"));
+    }
 }
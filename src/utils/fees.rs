@@ -0,0 +1,232 @@
+use anyhow::{Context, Result};
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{BlockNumber, U256},
+};
+use serde::{Deserialize, Serialize};
+
+use super::units::{self, format_gwei};
+
+/// Number of recent blocks sampled from `eth_feeHistory`
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Priority fee used when neither fee history rewards nor `eth_maxPriorityFeePerGas` are available
+const FALLBACK_PRIORITY_FEE: u64 = 1_500_000_000; // 1.5 gwei
+
+/// Headroom applied to the current blob base fee
+const BLOB_BASE_FEE_MULTIPLIER: u64 = 2;
+
+/// Named fee strategy used to derive EIP-1559 fees from the network
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeStrategy {
+    /// 10th percentile tip, max fee of 1.5x the base fee
+    Slow,
+    /// 50th percentile tip, max fee of 2x the base fee
+    #[default]
+    Normal,
+    /// 90th percentile tip, max fee of 3x the base fee
+    Fast,
+    /// Tip from the node's eth_maxPriorityFeePerGas, max fee of 2x the base fee
+    Node,
+}
+
+impl FeeStrategy {
+    /// Reward percentile requested from `eth_feeHistory` (`None` for node-suggested tips)
+    fn reward_percentile(self) -> Option<f64> {
+        match self {
+            FeeStrategy::Slow => Some(10.0),
+            FeeStrategy::Normal => Some(50.0),
+            FeeStrategy::Fast => Some(90.0),
+            FeeStrategy::Node => None,
+        }
+    }
+
+    /// Base fee multiplier in percent; leaves headroom for base fee growth while the
+    /// transaction travels to the signer and back
+    fn base_fee_multiplier_percent(self) -> u64 {
+        match self {
+            FeeStrategy::Slow => 150,
+            FeeStrategy::Normal | FeeStrategy::Node => 200,
+            FeeStrategy::Fast => 300,
+        }
+    }
+}
+
+/// Explicit fee values that take precedence over the strategy
+#[derive(Debug, Clone, Default)]
+pub struct FeeOverrides {
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub gas_price: Option<U256>,
+}
+
+/// Parse the `--max-fee`, `--priority-fee` and `--gas-price` values (gwei, or with a unit suffix)
+pub fn parse_overrides(
+    max_fee: Option<&str>,
+    priority_fee: Option<&str>,
+    gas_price: Option<&str>,
+) -> Result<FeeOverrides> {
+    Ok(FeeOverrides {
+        max_fee_per_gas: max_fee.map(units::parse_gwei).transpose().context("Invalid --max-fee")?,
+        max_priority_fee_per_gas: priority_fee.map(units::parse_gwei).transpose()
+            .context("Invalid --priority-fee")?,
+        gas_price: gas_price.map(units::parse_gwei).transpose().context("Invalid --gas-price")?,
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Fees {
+    Eip1559 {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
+    Legacy {
+        gas_price: U256,
+    },
+}
+
+/// Resolve transaction fees from overrides, falling back to the strategy and RPC data.
+///
+/// A `gas_price` override always produces a legacy transaction. Otherwise EIP-1559 fees
+/// are derived from `eth_feeHistory`; chains without EIP-1559 fall back to `eth_gasPrice`.
+pub async fn resolve_fees(
+    provider: &Provider<Http>,
+    strategy: FeeStrategy,
+    overrides: &FeeOverrides,
+) -> Result<Fees> {
+    if let Some(gas_price) = overrides.gas_price {
+        return Ok(Fees::Legacy { gas_price });
+    }
+
+    let percentiles: Vec<f64> = strategy.reward_percentile().into_iter().collect();
+    let history = provider
+        .fee_history(FEE_HISTORY_BLOCKS, BlockNumber::Latest, &percentiles)
+        .await;
+
+    // The last entry is the base fee of the next (pending) block
+    let base_fee = match &history {
+        Ok(h) => h.base_fee_per_gas.last().copied().filter(|b| !b.is_zero()),
+        Err(_) => None,
+    };
+
+    let Some(base_fee) = base_fee else {
+        if overrides.max_fee_per_gas.is_some() || overrides.max_priority_fee_per_gas.is_some() {
+            anyhow::bail!(
+                "Network does not report EIP-1559 base fees; use --gas-price instead of --max-fee/--priority-fee"
+            );
+        }
+        println!("EIP-1559 fee data unavailable, using legacy gas price");
+        let gas_price = provider
+            .get_gas_price()
+            .await
+            .context("Failed to fetch gas price")?;
+        return Ok(Fees::Legacy { gas_price });
+    };
+    println!("Base fee: {} gwei", format_gwei(base_fee));
+
+    let priority_fee = match overrides.max_priority_fee_per_gas {
+        Some(tip) => tip,
+        None => {
+            let from_history = history
+                .ok()
+                .filter(|_| strategy.reward_percentile().is_some())
+                .and_then(|h| median(h.reward.iter().filter_map(|r| r.first().copied()).collect()));
+            match from_history {
+                Some(tip) => tip,
+                None => node_priority_fee(provider)
+                    .await
+                    .unwrap_or_else(|| U256::from(FALLBACK_PRIORITY_FEE)),
+            }
+        }
+    };
+
+    let max_fee = match overrides.max_fee_per_gas {
+        Some(max_fee) => max_fee,
+        None => {
+            base_fee * U256::from(strategy.base_fee_multiplier_percent()) / U256::from(100u64)
+                + priority_fee
+        }
+    };
+
+    if max_fee < priority_fee {
+        anyhow::bail!(
+            "Max fee per gas ({} gwei) is lower than the priority fee ({} gwei)",
+            format_gwei(max_fee),
+            format_gwei(priority_fee)
+        );
+    }
+    if max_fee < base_fee {
+        println!(
+            "⚠ Max fee per gas ({} gwei) is below the current base fee ({} gwei); the transaction will not be included until the base fee drops",
+            format_gwei(max_fee),
+            format_gwei(base_fee)
+        );
+    }
+
+    Ok(Fees::Eip1559 {
+        max_fee_per_gas: max_fee,
+        max_priority_fee_per_gas: priority_fee,
+    })
+}
+
+/// Resolve fees from explicit values only, for offline preparation.
+///
+/// Either `gas_price` (legacy) or both `max_fee_per_gas` and `max_priority_fee_per_gas`
+/// must be set, since there is no network to fill in the rest.
+pub fn explicit_fees(overrides: &FeeOverrides) -> Result<Fees> {
+    if let Some(gas_price) = overrides.gas_price {
+        return Ok(Fees::Legacy { gas_price });
+    }
+
+    let (Some(max_fee), Some(priority_fee)) =
+        (overrides.max_fee_per_gas, overrides.max_priority_fee_per_gas)
+    else {
+        anyhow::bail!("Offline mode needs --gas-price, or both --max-fee and --priority-fee");
+    };
+
+    if max_fee < priority_fee {
+        anyhow::bail!(
+            "Max fee per gas ({} gwei) is lower than the priority fee ({} gwei)",
+            format_gwei(max_fee),
+            format_gwei(priority_fee)
+        );
+    }
+
+    Ok(Fees::Eip1559 {
+        max_fee_per_gas: max_fee,
+        max_priority_fee_per_gas: priority_fee,
+    })
+}
+
+/// Resolve the EIP-4844 max fee per blob gas: the override, or twice the current blob base fee
+/// from `eth_blobBaseFee` (it can rise by 12.5% per full block before the transaction lands)
+pub async fn resolve_blob_fee(provider: &Provider<Http>, max_blob_fee: Option<U256>) -> Result<U256> {
+    if let Some(fee) = max_blob_fee {
+        return Ok(fee);
+    }
+    let blob_base_fee: U256 = provider
+        .request("eth_blobBaseFee", ())
+        .await
+        .context("Failed to fetch blob base fee (eth_blobBaseFee); pass --max-blob-fee to set it manually")?;
+    println!("Blob base fee: {} gwei", format_gwei(blob_base_fee));
+    Ok((blob_base_fee * BLOB_BASE_FEE_MULTIPLIER).max(U256::one()))
+}
+
+/// Priority fee suggested by the node via `eth_maxPriorityFeePerGas`
+async fn node_priority_fee(provider: &Provider<Http>) -> Option<U256> {
+    provider
+        .request::<_, U256>("eth_maxPriorityFeePerGas", ())
+        .await
+        .ok()
+}
+
+/// Median of the non-zero samples (empty blocks report a zero reward)
+fn median(mut samples: Vec<U256>) -> Option<U256> {
+    samples.retain(|s| !s.is_zero());
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort();
+    Some(samples[samples.len() / 2])
+}
@@ -0,0 +1,148 @@
+use anyhow::{Context, Result};
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{
+        transaction::eip2930::{AccessList, AccessListWithGasUsed},
+        BlockNumber, Bytes, H160, H256, U256,
+    },
+};
+use serde_json::{json, Value};
+
+use super::typed_tx::DELEGATION_DESIGNATOR_PREFIX;
+
+/// Default safety multiplier applied to `eth_estimateGas` results
+pub const DEFAULT_GAS_MULTIPLIER: f64 = 1.2;
+
+/// Intrinsic gas charged per EIP-7702 authorization (partly refunded if the account exists)
+const PER_EMPTY_ACCOUNT_COST: u64 = 25_000;
+
+/// The parts of a prepared transaction that are sent to `eth_call`, `eth_estimateGas`
+/// and `eth_createAccessList`
+#[derive(Debug, Clone, Default)]
+pub struct CallRequest<'a> {
+    pub from: H160,
+    pub to: Option<H160>,
+    pub data: &'a [u8],
+    pub value: U256,
+    pub access_list: Option<&'a AccessList>,
+    /// EIP-4844 fields, so contracts reading `blobhash` see the real blobs
+    pub blob_versioned_hashes: &'a [H256],
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// EIP-7702 delegation `(authority, delegate)`, applied through a state override
+    /// since unsigned authorizations cannot be passed to the node
+    pub delegation: Option<(H160, H160)>,
+}
+
+impl CallRequest<'_> {
+    /// JSON-RPC transaction object (ethers' request types have no blob fields)
+    pub fn to_json(&self) -> Value {
+        let mut tx = json!({
+            "from": self.from,
+            "data": Bytes::from(self.data.to_vec()),
+            "value": self.value,
+        });
+        if let Some(to) = self.to {
+            tx["to"] = json!(to);
+        }
+        if let Some(access_list) = self.access_list {
+            tx["accessList"] = json!(access_list);
+        }
+        if !self.blob_versioned_hashes.is_empty() {
+            tx["blobVersionedHashes"] = json!(self.blob_versioned_hashes);
+        }
+        if let Some(max_fee_per_blob_gas) = self.max_fee_per_blob_gas {
+            tx["maxFeePerBlobGas"] = json!(max_fee_per_blob_gas);
+        }
+        tx
+    }
+
+    /// `[tx, "pending"]` parameters, plus a state override installing the delegation
+    /// designator on the authority when a delegation is set
+    pub fn params(&self) -> Vec<Value> {
+        let mut params = vec![self.to_json(), json!(BlockNumber::Pending)];
+        if let Some((authority, delegate)) = self.delegation {
+            let code = if delegate.is_zero() {
+                Bytes::new()
+            } else {
+                Bytes::from([&DELEGATION_DESIGNATOR_PREFIX[..], delegate.as_bytes()].concat())
+            };
+            params.push(json!({ format!("{:?}", authority): { "code": code } }));
+        }
+        params
+    }
+}
+
+/// Estimate the gas limit for a transaction via `eth_estimateGas`.
+///
+/// The estimate is scaled by `multiplier` to leave headroom for state changes between
+/// prepare and broadcast, then capped at the latest block's gas limit. When an access
+/// list is given it is included in the estimate, since it changes the gas charged.
+pub async fn estimate_gas_limit(
+    provider: &Provider<Http>,
+    call: &CallRequest<'_>,
+    multiplier: f64,
+) -> Result<u64> {
+    if !multiplier.is_finite() || multiplier < 1.0 {
+        anyhow::bail!("Gas multiplier must be at least 1.0 (got {})", multiplier);
+    }
+
+    let estimate: U256 = provider
+        .request("eth_estimateGas", call.params())
+        .await
+        .context("eth_estimateGas failed (the transaction may revert); pass --gas-limit to set it manually")?;
+    println!("Estimated gas: {}", estimate);
+
+    // The state override does not charge for processing the authorization
+    let estimate = match call.delegation {
+        Some(_) => estimate + U256::from(PER_EMPTY_ACCOUNT_COST),
+        None => estimate,
+    };
+
+    // Scale in per-mille to stay in integer arithmetic
+    let scaled = estimate * U256::from((multiplier * 1000.0).round() as u64) / U256::from(1000u64);
+
+    let block = provider
+        .get_block(BlockNumber::Latest)
+        .await
+        .context("Failed to fetch latest block")?
+        .ok_or_else(|| anyhow::anyhow!("Latest block not available from RPC"))?;
+
+    let gas_limit = if scaled > block.gas_limit {
+        println!(
+            "⚠ Gas limit {} exceeds the block gas limit, capping at {}",
+            scaled, block.gas_limit
+        );
+        block.gas_limit
+    } else {
+        scaled
+    };
+
+    if gas_limit > U256::from(u64::MAX) {
+        anyhow::bail!("Gas limit {} does not fit in 64 bits", gas_limit);
+    }
+    Ok(gas_limit.as_u64())
+}
+
+/// Generate an EIP-2930 access list for the transaction via `eth_createAccessList`.
+///
+/// A delegation is applied with the same state override as simulation and estimation,
+/// so the list is traced against the delegated code; nodes that take no override fail
+/// rather than trace the undelegated account.
+pub async fn create_access_list(provider: &Provider<Http>, call: &CallRequest<'_>) -> Result<AccessList> {
+    let result: AccessListWithGasUsed = provider
+        .request("eth_createAccessList", call.params())
+        .await
+        .context(match call.delegation {
+            Some(_) => "eth_createAccessList failed (the node may not support it, or not with the state override for --delegate; prepare without --access-list)",
+            None => "eth_createAccessList failed (the node may not support it)",
+        })?;
+
+    let keys: usize = result.access_list.0.iter().map(|item| item.storage_keys.len()).sum();
+    println!(
+        "Access list: {} address(es), {} storage key(s); gas used with the list: {}",
+        result.access_list.0.len(),
+        keys,
+        result.gas_used
+    );
+    Ok(result.access_list)
+}
@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use ethers::abi::{Abi, Constructor, Function, Param, Token};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs;
+
+use super::abi_args;
+use crate::types::prepare_output::{ArtifactSource, CallMetadata};
+
+/// Function or constructor described by a [`CallMetadata`]
+pub enum Fragment {
+    Function(Function),
+    Constructor(Constructor),
+}
+
+impl Fragment {
+    pub fn inputs(&self) -> &[Param] {
+        match self {
+            Fragment::Function(function) => &function.inputs,
+            Fragment::Constructor(constructor) => &constructor.inputs,
+        }
+    }
+}
+
+/// Path and SHA-256 of an artifact file
+pub fn artifact_source(path: &str) -> Result<ArtifactSource> {
+    let content = fs::read(path).with_context(|| format!("Failed to read contract file: {}", path))?;
+    Ok(ArtifactSource {
+        path: path.to_string(),
+        sha256: hex::encode(Sha256::digest(&content)),
+    })
+}
+
+/// Metadata for a function call, with the arguments decoded from the encoded calldata
+pub fn for_function(function: &Function, call_data: &[u8], artifact: Option<ArtifactSource>) -> Result<CallMetadata> {
+    let tokens = function
+        .decode_input(call_data.get(4..).unwrap_or_default())
+        .context("Failed to decode the encoded function call")?;
+    let abi = Abi {
+        functions: [(function.name.clone(), vec![function.clone()])].into(),
+        ..Default::default()
+    };
+    Ok(CallMetadata {
+        abi: single_entry(&abi)?,
+        args: tokens.iter().map(abi_args::token_to_value).collect(),
+        artifact,
+    })
+}
+
+/// Metadata for a deployment; a contract without a constructor is recorded as one
+/// without inputs
+pub fn for_constructor(
+    constructor: Option<&Constructor>,
+    tokens: &[Token],
+    artifact: Option<ArtifactSource>,
+) -> Result<CallMetadata> {
+    let abi = Abi {
+        constructor: Some(constructor.cloned().unwrap_or(Constructor { inputs: vec![] })),
+        ..Default::default()
+    };
+    Ok(CallMetadata {
+        abi: single_entry(&abi)?,
+        args: tokens.iter().map(abi_args::token_to_value).collect(),
+        artifact,
+    })
+}
+
+fn single_entry(abi: &Abi) -> Result<Value> {
+    match serde_json::to_value(abi).context("Failed to serialize ABI entry")? {
+        Value::Array(mut entries) if entries.len() == 1 => Ok(entries.remove(0)),
+        _ => anyhow::bail!("Expected exactly one ABI entry"),
+    }
+}
+
+/// Parse the ABI entry of the metadata
+pub fn fragment(metadata: &CallMetadata) -> Result<Fragment> {
+    let abi: Abi = serde_json::from_value(Value::Array(vec![metadata.abi.clone()]))
+        .context("Invalid ABI entry in the decoding metadata")?;
+    if let Some(constructor) = abi.constructor {
+        return Ok(Fragment::Constructor(constructor));
+    }
+    abi.functions()
+        .next()
+        .cloned()
+        .map(Fragment::Function)
+        .context("The decoding metadata holds neither a function nor a constructor")
+}
+
+/// Check that `init_code` is exactly `bytecode` followed by the encoded constructor
+/// arguments, so the deployment runs the artifact's code and nothing else
+pub fn verify_init_code(init_code: &[u8], bytecode: &[u8], tokens: &[Token]) -> Result<()> {
+    if init_code != [bytecode, &ethers::abi::encode(tokens)].concat() {
+        anyhow::bail!(
+            "The init code is not the artifact's bytecode followed by the constructor arguments; \
+             the transaction would deploy different code"
+        );
+    }
+    Ok(())
+}
+
+/// Re-encode the metadata and check it against `data`: the whole calldata of a call, or
+/// the tail of the init code of a deployment. Returns the fragment and argument tokens.
+pub fn verify(metadata: &CallMetadata, data: &[u8]) -> Result<(Fragment, Vec<Token>)> {
+    let fragment = fragment(metadata)?;
+    let tokens = abi_args::tokenize_args("Decoding metadata", &metadata.args, fragment.inputs())?;
+    match &fragment {
+        Fragment::Function(function) => {
+            let encoded = function
+                .encode_input(&tokens)
+                .context("Failed to re-encode the decoding metadata")?;
+            if encoded != data {
+                anyhow::bail!(
+                    "The decoding metadata ({}) does not encode to the transaction data",
+                    super::contract::canonical_signature(function)
+                );
+            }
+        }
+        Fragment::Constructor(_) => {
+            // The arguments follow the bytecode, which is not repeated in the metadata;
+            // see `verify_init_code` for checking the bytecode against an artifact
+            if !data.ends_with(&ethers::abi::encode(&tokens)) {
+                anyhow::bail!("The constructor arguments in the decoding metadata do not match the init code");
+            }
+        }
+    }
+    Ok((fragment, tokens))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::ParamType;
+    use ethers::types::{H160, U256};
+
+    fn constructor(inputs: Vec<ParamType>) -> Constructor {
+        Constructor {
+            inputs: inputs
+                .into_iter()
+                .enumerate()
+                .map(|(i, kind)| Param { name: format!("arg{}", i), kind, internal_type: None })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn function_metadata_must_encode_to_the_calldata() {
+        let function = abi_args::parse_signature("transfer(address to, uint256 amount)").unwrap();
+        let tokens = [Token::Address(H160::repeat_byte(0x11)), Token::Uint(U256::from(1000))];
+        let call_data = function.encode_input(&tokens).unwrap();
+
+        let metadata = for_function(&function, &call_data, None).unwrap();
+        let (fragment, decoded) = verify(&metadata, &call_data).unwrap();
+        assert!(matches!(fragment, Fragment::Function(f) if f.name == "transfer"));
+        assert_eq!(decoded, tokens);
+
+        let mut tampered = call_data.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(verify(&metadata, &tampered).is_err());
+        assert!(verify(&metadata, &call_data[..call_data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn constructor_metadata_checks_the_arguments_after_the_bytecode() {
+        let bytecode = vec![0x60, 0x80, 0x60, 0x40];
+        let constructor = constructor(vec![ParamType::Uint(256)]);
+        let tokens = [Token::Uint(U256::from(42))];
+        let init_code = [&bytecode[..], &ethers::abi::encode(&tokens)].concat();
+
+        let metadata = for_constructor(Some(&constructor), &tokens, None).unwrap();
+        assert_eq!(verify(&metadata, &init_code).unwrap().1, tokens);
+        verify_init_code(&init_code, &bytecode, &tokens).unwrap();
+
+        let other = [&bytecode[..], &ethers::abi::encode(&[Token::Uint(U256::from(43))])].concat();
+        assert!(verify(&metadata, &other).is_err());
+    }
+
+    #[test]
+    fn init_code_without_constructor_inputs_is_only_checked_against_the_bytecode() {
+        let bytecode = vec![0x60, 0x80, 0x60, 0x40];
+        let metadata = for_constructor(None, &[], None).unwrap();
+
+        // Metadata alone cannot tell the bytecode apart from any other
+        let tampered = vec![0x60, 0x80, 0x60, 0x41];
+        assert!(verify(&metadata, &tampered).is_ok());
+
+        verify_init_code(&bytecode, &bytecode, &[]).unwrap();
+        assert!(verify_init_code(&tampered, &bytecode, &[]).is_err());
+        assert!(verify_init_code(&[&bytecode[..], &[0]].concat(), &bytecode, &[]).is_err());
+        assert!(verify_init_code(&bytecode[..3], &bytecode, &[]).is_err());
+    }
+}
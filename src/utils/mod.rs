@@ -1,2 +1,14 @@
+pub mod abi_args;
+pub mod blob;
+pub mod chains;
 pub mod contract;
+pub mod fees;
+pub mod gas;
+pub mod metadata;
+pub mod multicall;
+pub mod review;
 pub mod rpc;
+pub mod signatures;
+pub mod simulate;
+pub mod typed_tx;
+pub mod units;
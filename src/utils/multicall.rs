@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use ethers::{
+    abi::{Function, Token},
+    types::{H160, U256},
+};
+
+use super::abi_args;
+use crate::types::prepare_output::MulticallCall;
+
+/// `calls` is a list of `(target, allowFailure, callData)`
+const AGGREGATE3: &str = "aggregate3((address,bool,bytes)[] calls) payable returns ((bool,bytes)[])";
+/// `calls` is a list of `(target, allowFailure, value, callData)`
+const AGGREGATE3_VALUE: &str =
+    "aggregate3Value((address,bool,uint256,bytes)[] calls) payable returns ((bool,bytes)[])";
+
+/// The Multicall3 function used for `calls`: `aggregate3Value` when any call sends value,
+/// `aggregate3` otherwise
+pub fn aggregate_function(calls: &[MulticallCall]) -> Function {
+    let signature = if calls.iter().any(|call| !call.value.is_zero()) {
+        AGGREGATE3_VALUE
+    } else {
+        AGGREGATE3
+    };
+    abi_args::parse_signature(signature).expect("Multicall3 signatures are valid")
+}
+
+/// Encode the Multicall3 call that runs `calls` in order
+pub fn encode(calls: &[MulticallCall]) -> Result<Vec<u8>> {
+    let function = aggregate_function(calls);
+    let with_value = function.name == "aggregate3Value";
+    let items = calls
+        .iter()
+        .enumerate()
+        .map(|(i, call)| {
+            let target: H160 = call
+                .target
+                .parse()
+                .with_context(|| format!("Invalid target address in call {}: {}", i + 1, call.target))?;
+            let call_data = hex::decode(call.call_data.trim_start_matches("0x"))
+                .with_context(|| format!("Invalid calldata in call {}", i + 1))?;
+            let mut fields = vec![Token::Address(target), Token::Bool(call.allow_failure)];
+            if with_value {
+                fields.push(Token::Uint(call.value));
+            }
+            fields.push(Token::Bytes(call_data));
+            Ok(Token::Tuple(fields))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    function
+        .encode_input(&[Token::Array(items)])
+        .context("Failed to encode Multicall3 call")
+}
+
+/// Total value forwarded to the calls, which the transaction must send to Multicall3
+pub fn total_value(calls: &[MulticallCall]) -> Result<U256> {
+    calls.iter().try_fold(U256::zero(), |total, call| {
+        total
+            .checked_add(call.value)
+            .context("Total value of the calls overflows")
+    })
+}
+
+/// Decode a call with the function its `function` label names. The label comes from the
+/// unsigned file, so it is only trusted once its selector matches the calldata and the
+/// arguments re-encode to exactly that calldata. Calls without a label decode to `None`.
+pub fn decode_call(call: &MulticallCall) -> Result<Option<(Function, Vec<Token>)>> {
+    let Some(label) = &call.function else { return Ok(None) };
+    let function = abi_args::parse_signature(label)?;
+    let call_data = hex::decode(call.call_data.trim_start_matches("0x"))
+        .with_context(|| format!("Invalid calldata in the call to {}", call.target))?;
+    if call_data.get(..4) != Some(&function.short_signature()[..]) {
+        anyhow::bail!(
+            "The call to {} is labelled {}, but its calldata starts with 0x{}",
+            call.target,
+            label,
+            hex::encode(&call_data[..call_data.len().min(4)])
+        );
+    }
+    let tokens = function
+        .decode_input(&call_data[4..])
+        .ok()
+        .filter(|tokens| function.encode_input(tokens).is_ok_and(|encoded| encoded == call_data))
+        .with_context(|| format!("The calldata of the call to {} does not encode arguments of {}", call.target, label))?;
+    Ok(Some((function, tokens)))
+}
+
+/// List the calls of a batch, one per line
+pub fn print_calls(calls: &[MulticallCall]) {
+    println!("Calls: {}", calls.len());
+    for (i, call) in calls.iter().enumerate() {
+        let function = match (&call.function, call.call_data.get(..8)) {
+            (Some(function), _) => function.clone(),
+            (None, Some(selector)) => format!("0x{}", selector),
+            (None, None) => "(no calldata)".to_string(),
+        };
+        print!("  {}. {} {}", i + 1, call.target, function);
+        if !call.value.is_zero() {
+            print!(", value {} wei", call.value);
+        }
+        if call.allow_failure {
+            print!(" (may fail)");
+        }
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(function: Option<&str>, call_data: Vec<u8>) -> MulticallCall {
+        MulticallCall {
+            target: "0x742D35CC6634c0532925A3b844BC9E7595F0BEb0".to_string(),
+            value: U256::zero(),
+            allow_failure: false,
+            call_data: hex::encode(call_data),
+            function: function.map(str::to_string),
+        }
+    }
+
+    fn transfer_ownership() -> Vec<u8> {
+        let function = abi_args::parse_signature("transferOwnership(address)").unwrap();
+        function.encode_input(&[Token::Address(H160::repeat_byte(0x11))]).unwrap()
+    }
+
+    #[test]
+    fn labelled_call_decodes_with_its_label() {
+        let (function, tokens) = decode_call(&call(Some("transferOwnership(address)"), transfer_ownership()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(function.name, "transferOwnership");
+        assert_eq!(tokens, [Token::Address(H160::repeat_byte(0x11))]);
+        assert!(decode_call(&call(None, transfer_ownership())).unwrap().is_none());
+    }
+
+    #[test]
+    fn label_must_match_the_calldata() {
+        assert!(decode_call(&call(Some("pause()"), transfer_ownership())).is_err());
+        assert!(decode_call(&call(Some("pause()"), Vec::new())).is_err());
+
+        let mut trailing = transfer_ownership();
+        trailing.push(0);
+        assert!(decode_call(&call(Some("transferOwnership(address)"), trailing)).is_err());
+    }
+
+    #[test]
+    fn value_selects_aggregate3_value() {
+        let mut calls = vec![call(None, transfer_ownership())];
+        assert_eq!(aggregate_function(&calls).name, "aggregate3");
+        calls.push(MulticallCall { value: U256::from(5), ..call(None, Vec::new()) });
+        assert_eq!(aggregate_function(&calls).name, "aggregate3Value");
+        assert_eq!(total_value(&calls).unwrap(), U256::from(5));
+        assert_eq!(encode(&calls).unwrap()[..4], aggregate_function(&calls).short_signature());
+    }
+}
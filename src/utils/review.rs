@@ -0,0 +1,424 @@
+use anyhow::{Context, Result};
+use ethers::{
+    abi::{Abi, Param, Token},
+    types::{Address, U256},
+    utils::to_checksum,
+};
+use serde_json::Value;
+use std::fs;
+
+use super::metadata::{self, Fragment};
+use super::{abi_args, chains, contract, multicall, signatures, units};
+use crate::types::prepare_output::{Authorization, CallMetadata, MulticallCall, UnsignedTransaction};
+
+/// Blob gas used by each blob (EIP-4844 `GAS_PER_BLOB`)
+const GAS_PER_BLOB: u64 = 131_072;
+
+/// ABI used to decode the reviewed calldata
+pub struct ReviewAbi {
+    pub abi: Abi,
+    /// SHA-256 of the file, compared with the artifact recorded by `prepare`
+    pub sha256: String,
+    /// Creation bytecode, when the ABI came from an artifact; locates the constructor
+    /// arguments of a deployment
+    pub bytecode: Option<Vec<u8>>,
+}
+
+/// Load an ABI from a compiled contract JSON or a plain ABI array
+pub fn load_abi(path: &str) -> Result<ReviewAbi> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read ABI file: {}", path))?;
+    let json: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse ABI file: {}", path))?;
+
+    let sha256 = metadata::artifact_source(path)?.sha256;
+
+    if json.is_array() {
+        let abi = serde_json::from_value(json).context("Failed to parse ABI")?;
+        return Ok(ReviewAbi { abi, sha256, bytecode: None });
+    }
+
+    let artifact = contract::parse_contract_json(path, None)?;
+    let abi = serde_json::from_value(artifact.abi).context("Failed to parse ABI")?;
+    // Unlinked bytecode does not decode, and is only needed for constructor arguments
+    let bytecode = hex::decode(&artifact.bytecode).ok().filter(|b| !b.is_empty());
+    Ok(ReviewAbi { abi, sha256, bytecode })
+}
+
+/// Most a transaction can pay in fees: the whole gas limit at the fee cap, plus the
+/// blob gas at the blob fee cap. Fails when the amounts do not fit in 256 bits, which
+/// no valid transaction can reach
+pub fn fee_ceiling(fee_cap: U256, gas_limit: U256, blob_fee_cap: U256, blobs: usize) -> Result<U256> {
+    let blob_gas = U256::from(blobs as u64).checked_mul(U256::from(GAS_PER_BLOB));
+    fee_cap
+        .checked_mul(gas_limit)
+        .zip(blob_gas.and_then(|blob_gas| blob_fee_cap.checked_mul(blob_gas)))
+        .and_then(|(gas_fee, blob_fee)| gas_fee.checked_add(blob_fee))
+        .context("Fee ceiling overflows 256 bits; the fee caps or gas limit are invalid")
+}
+
+/// Most the transaction can pay in fees (see [`fee_ceiling`])
+pub fn max_fee(unsigned_tx: &UnsignedTransaction) -> Result<U256> {
+    fee_ceiling(
+        unsigned_tx.max_fee_per_gas.or(unsigned_tx.gas_price).unwrap_or_default(),
+        U256::from(unsigned_tx.gas_limit),
+        unsigned_tx.max_fee_per_blob_gas.unwrap_or_default(),
+        unsigned_tx.blob_versioned_hashes.as_ref().map_or(0, Vec::len),
+    )
+}
+
+/// Most the transaction can take from the sender: fees plus the value sent
+pub fn max_cost(unsigned_tx: &UnsignedTransaction) -> Result<U256> {
+    max_fee(unsigned_tx)?
+        .checked_add(unsigned_tx.value)
+        .context("Max total cost overflows 256 bits; the value is invalid")
+}
+
+/// Print what the transaction does and what it can cost, in a form a reviewer can check
+/// against the intended operation
+pub fn print_review(unsigned_tx: &UnsignedTransaction, abi: Option<&ReviewAbi>) -> Result<()> {
+    let symbol = chains::native_symbol(unsigned_tx.chain_id);
+    let (max_fee, max_cost) = (max_fee(unsigned_tx)?, max_cost(unsigned_tx)?);
+
+    println!("\n═══════════════════ TRANSACTION REVIEW ═══════════════════");
+    println!("  Chain:          {}", chains::describe(unsigned_tx.chain_id));
+    if let Some(from) = &unsigned_tx.from {
+        let from: Address = from.parse().context("Invalid from address")?;
+        println!("  From:           {}", to_checksum(&from, None));
+    }
+    println!("  Nonce:          {}", unsigned_tx.nonce);
+    match &unsigned_tx.to {
+        Some(to) => {
+            let to: Address = to.parse().context("Invalid to address")?;
+            println!("  To:             {}", to_checksum(&to, None));
+        }
+        None => println!("  To:             (contract deployment)"),
+    }
+    println!("  Value:          {} {}", units::format_ether(unsigned_tx.value), symbol);
+    println!("  Gas limit:      {}", unsigned_tx.gas_limit);
+    match (unsigned_tx.max_fee_per_gas, unsigned_tx.gas_price) {
+        (Some(max_fee_per_gas), _) => println!(
+            "  Max fee:        {} gwei per gas (priority fee {} gwei)",
+            units::format_gwei(max_fee_per_gas),
+            units::format_gwei(unsigned_tx.max_priority_fee_per_gas.unwrap_or_default())
+        ),
+        (None, Some(gas_price)) => println!("  Gas price:      {} gwei", units::format_gwei(gas_price)),
+        (None, None) => {}
+    }
+    println!("  Fee ceiling:    {} {}", units::format_ether(max_fee), symbol);
+    println!("  Max total cost: {} {}", units::format_ether(max_cost), symbol);
+
+    let data = hex::decode(&unsigned_tx.data).context("Failed to decode transaction data")?;
+    println!("  ──────────────────────────────────────────────────────────");
+    let deploys = unsigned_tx.to.is_none() || unsigned_tx.create2.is_some();
+    if let Some(decoded) = &unsigned_tx.decoded {
+        let init_code = deploys.then(|| print_deployment(unsigned_tx, &data));
+        print_metadata(decoded, &data, init_code, abi)?;
+    } else if deploys {
+        let init_code = print_deployment(unsigned_tx, &data);
+        print_constructor(init_code, abi)?;
+    } else if let Some(calls) = &unsigned_tx.multicall {
+        println!("  Action:         Multicall3 batch of {} call(s)", calls.len());
+        for (i, call) in calls.iter().enumerate() {
+            print_multicall_call(i, call, symbol, abi)?;
+        }
+    } else if data.is_empty() {
+        println!("  Action:         plain transfer");
+    } else {
+        print_call(&data, abi);
+    }
+    println!("══════════════════════════════════════════════════════════");
+
+    // EIP-7702: the account signs over its own code, so the delegation is shown on its own
+    if let Some(authorizations) = &unsigned_tx.authorization_list {
+        print_delegation(unsigned_tx, authorizations)?;
+    }
+    Ok(())
+}
+
+/// Show what the account will delegate to.
+///
+/// The authorization is only valid when signed by the sending account with the
+/// transaction nonce + 1, so other combinations are rejected here.
+fn print_delegation(unsigned_tx: &UnsignedTransaction, authorizations: &[Authorization]) -> Result<()> {
+    println!("\n════════════════ EIP-7702 DELEGATION ════════════════");
+    match &unsigned_tx.from {
+        Some(from) => {
+            let from: Address = from.parse().context("Invalid from address")?;
+            println!("  Account:      {} (the signing keystore)", to_checksum(&from, None));
+        }
+        None => println!("  Account:      the signing keystore"),
+    }
+    for authorization in authorizations {
+        let delegate: Address = authorization
+            .address
+            .parse()
+            .context("Invalid delegate address in authorization")?;
+        if delegate.is_zero() {
+            println!("  Delegate to:  none (clears the current delegation)");
+        } else {
+            println!("  Delegate to:  {}", to_checksum(&delegate, None));
+        }
+        match authorization.chain_id {
+            0 => println!("  Valid on:     ANY chain"),
+            id => println!("  Valid on:     chain {}", id),
+        }
+        println!("  Auth nonce:   {}", authorization.nonce);
+
+        if authorization.chain_id != 0 && authorization.chain_id != unsigned_tx.chain_id {
+            anyhow::bail!(
+                "Authorization is for chain {} but the transaction is for chain {}",
+                authorization.chain_id,
+                unsigned_tx.chain_id
+            );
+        }
+        if authorization.nonce != unsigned_tx.nonce + 1 {
+            anyhow::bail!(
+                "Authorization nonce {} would be ignored: the sending account needs the transaction nonce + 1 ({})",
+                authorization.nonce,
+                unsigned_tx.nonce + 1
+            );
+        }
+    }
+    println!("═════════════════════════════════════════════════════");
+    println!("Once included, every call to this account runs the delegate's code, which can");
+    println!("move everything the account holds. Only delegate to audited code you trust.");
+    Ok(())
+}
+
+/// Show how the contract is deployed; returns the init code, without the CREATE2 salt
+fn print_deployment<'a>(unsigned_tx: &UnsignedTransaction, data: &'a [u8]) -> &'a [u8] {
+    // A CREATE2 factory receives the salt followed by the init code
+    let init_code = match &unsigned_tx.create2 {
+        Some(deployment) => {
+            println!("  Action:         deploy a contract with CREATE2");
+            println!("  Factory:        {}", deployment.factory);
+            println!("  Salt:           {:?}", deployment.salt);
+            data.get(32..).unwrap_or_default()
+        }
+        None => {
+            println!("  Action:         deploy a contract");
+            data
+        }
+    };
+    println!("  Init code:      {} bytes", init_code.len());
+    if let Some(address) = &unsigned_tx.contract_address {
+        println!("  Contract at:    {}", address);
+    }
+    init_code
+}
+
+/// Decode the constructor arguments of a deployment without metadata, from the bytecode
+/// of the `--abi` artifact; init code that is not that bytecode is refused
+fn print_constructor(init_code: &[u8], abi: Option<&ReviewAbi>) -> Result<()> {
+    let bytecode = abi.and_then(|abi| abi.bytecode.as_deref());
+    if let (Some(abi), Some(bytecode)) = (abi, bytecode) {
+        let inputs = abi.abi.constructor().map_or(&[][..], |constructor| &constructor.inputs);
+        let tokens = init_code
+            .strip_prefix(bytecode)
+            .and_then(|encoded| ethers::abi::decode(&param_types(inputs), encoded).ok())
+            .unwrap_or_default();
+        metadata::verify_init_code(init_code, bytecode, &tokens)?;
+        if !tokens.is_empty() {
+            println!("  Constructor:");
+            print_arguments(inputs, &tokens);
+        }
+    }
+    print_bytecode_check(abi);
+    Ok(())
+}
+
+/// State whether the deployed bytecode was compared with the `--abi` artifact; callers
+/// have already refused init code that does not match it
+fn print_bytecode_check(abi: Option<&ReviewAbi>) {
+    match abi.map(|abi| abi.bytecode.is_some()) {
+        Some(true) => println!("  ✓ Init code is the bytecode of the --abi artifact followed by the constructor arguments"),
+        Some(false) => println!("  ⚠ Bytecode UNVERIFIED: --abi holds no linked bytecode to compare the init code with"),
+        None => println!("  ⚠ Bytecode UNVERIFIED: pass the contract artifact with --abi to check the init code"),
+    }
+}
+
+/// Decode calldata with the supplied ABI, else with the bundled signature database
+pub fn print_call(data: &[u8], abi: Option<&ReviewAbi>) {
+    let Some(selector) = data.get(..4) else {
+        println!("  Action:         call with {} byte(s) of data", data.len());
+        return;
+    };
+    let function = abi.and_then(|abi| {
+        abi.abi
+            .functions()
+            .find(|function| function.short_signature()[..] == *selector)
+    });
+    if let Some(function) = function {
+        println!("  Function:       {}", contract::canonical_signature(function));
+        match function.decode_input(&data[4..]) {
+            Ok(tokens) => print_arguments(&function.inputs, &tokens),
+            Err(_) => println!("  ⚠ Arguments do not decode with the supplied ABI"),
+        }
+        return;
+    }
+    if abi.is_some() {
+        println!("  ⚠ Selector 0x{} not found in the supplied ABI", hex::encode(selector));
+    }
+
+    // Without an ABI, fall back to the bundled signatures; a selector can belong to
+    // several of them, and then the calldata alone cannot tell which one is called
+    match signatures::decode(data).as_slice() {
+        [] => {
+            println!("  Action:         call 0x{} ({} bytes of data)", hex::encode(selector), data.len());
+            if signatures::lookup(selector).is_empty() {
+                println!("  Selector not in the bundled signature database; pass --abi to decode it");
+            } else {
+                println!("  ⚠ Selector is in the bundled signature database, but the arguments do not decode");
+            }
+        }
+        [(function, tokens)] => {
+            println!("  Function:       {} (bundled signature database)", contract::canonical_signature(function));
+            print_arguments(&function.inputs, tokens);
+            // The contract may still implement one of the others, with calldata it rejects
+            let others: Vec<String> = signatures::lookup(selector)
+                .iter()
+                .filter(|other| other.signature() != function.signature())
+                .map(contract::canonical_signature)
+                .collect();
+            if !others.is_empty() {
+                println!(
+                    "  ⚠ Selector 0x{} is shared with {} in the bundled database; the calldata does not decode with it",
+                    hex::encode(selector),
+                    others.join(", ")
+                );
+            }
+        }
+        matches => {
+            println!(
+                "  ⚠ Selector 0x{} matches {} signatures in the bundled database, and the calldata decodes with each:",
+                hex::encode(selector),
+                matches.len()
+            );
+            for (function, tokens) in matches {
+                println!("  Function:       {}", contract::canonical_signature(function));
+                print_arguments(&function.inputs, tokens);
+            }
+        }
+    }
+}
+
+/// Show the function or constructor recorded by `prepare`, and where its ABI came from.
+///
+/// The metadata of a deployment only covers the constructor arguments, so the bytecode
+/// in front of them (`init_code`) is compared with the `--abi` artifact when given
+fn print_metadata(
+    decoded: &CallMetadata,
+    data: &[u8],
+    init_code: Option<&[u8]>,
+    abi: Option<&ReviewAbi>,
+) -> Result<()> {
+    let (fragment, tokens) = metadata::verify(decoded, data)?;
+    if let (Some(init_code), Some(bytecode)) = (init_code, abi.and_then(|abi| abi.bytecode.as_deref())) {
+        metadata::verify_init_code(init_code, bytecode, &tokens)?;
+    }
+    match &fragment {
+        Fragment::Function(function) => {
+            println!("  Function:       {}", contract::canonical_signature(function));
+        }
+        Fragment::Constructor(_) if tokens.is_empty() => println!("  Constructor:    no arguments"),
+        Fragment::Constructor(_) => println!("  Constructor:"),
+    }
+    print_arguments(fragment.inputs(), &tokens);
+
+    match &decoded.artifact {
+        Some(artifact) => {
+            println!("  Artifact:       {}", artifact.path);
+            println!("  SHA-256:        {}", artifact.sha256);
+            match abi {
+                Some(abi) if abi.sha256 != artifact.sha256 => {
+                    println!("  ⚠ --abi file differs from the artifact used by prepare")
+                }
+                // A matching hash only says the files are the same; a deployment's
+                // bytecode is checked on its own below
+                Some(_) if init_code.is_none() => println!("  ✓ --abi file is the artifact used by prepare"),
+                _ => {}
+            }
+        }
+        None => println!("  ABI source:     function signature given to prepare"),
+    }
+    if init_code.is_some() {
+        print_bytecode_check(abi);
+    }
+    Ok(())
+}
+
+/// Show one call of a Multicall3 batch. A labelled call is decoded with its label, which
+/// must match the calldata; an unlabelled one is decoded like a top-level call
+fn print_multicall_call(index: usize, call: &MulticallCall, symbol: &str, abi: Option<&ReviewAbi>) -> Result<()> {
+    let target: Address = call.target.parse().context("Invalid call target")?;
+    println!("  ── Call {} ──", index + 1);
+    println!("  To:             {}", to_checksum(&target, None));
+    if !call.value.is_zero() {
+        println!("  Value:          {} {}", units::format_ether(call.value), symbol);
+    }
+    if call.allow_failure {
+        println!("  May fail:       yes (the batch continues if this call reverts)");
+    }
+    match multicall::decode_call(call)? {
+        Some((function, tokens)) => {
+            println!("  Function:       {}", contract::canonical_signature(&function));
+            print_arguments(&function.inputs, &tokens);
+        }
+        None => {
+            let data = hex::decode(call.call_data.trim_start_matches("0x")).context("Invalid call data")?;
+            if data.is_empty() {
+                println!("  Action:         plain transfer");
+            } else {
+                print_call(&data, abi);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_arguments(inputs: &[Param], tokens: &[Token]) {
+    for (i, (param, token)) in inputs.iter().zip(tokens).enumerate() {
+        let name = if param.name.is_empty() { format!("[{}]", i) } else { param.name.clone() };
+        println!("    {} ({}): {}", name, param.kind, abi_args::format_token(token));
+    }
+}
+
+fn param_types(inputs: &[Param]) -> Vec<ethers::abi::ParamType> {
+    inputs.iter().map(|param| param.kind.clone()).collect()
+}
+
+/// Ask the operator to type `yes`; anything else aborts with `refusal`
+pub fn confirm(prompt: &str, refusal: &str) -> Result<()> {
+    println!("\n{}", prompt);
+    let mut answer = String::new();
+    std::io::stdin()
+        .read_line(&mut answer)
+        .context("Failed to read confirmation")?;
+    if answer.trim() != "yes" {
+        anyhow::bail!("{}", refusal);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_ceiling_covers_gas_and_blob_gas() {
+        let gwei = U256::exp10(9);
+        assert_eq!(fee_ceiling(gwei * 30, U256::from(21_000), U256::zero(), 0).unwrap(), gwei * 630_000);
+        assert_eq!(
+            fee_ceiling(gwei * 30, U256::from(21_000), gwei * 10, 1).unwrap(),
+            gwei * (630_000 + 1_310_720)
+        );
+    }
+
+    #[test]
+    fn fee_ceiling_overflow_is_an_error() {
+        assert!(fee_ceiling(U256::MAX, U256::from(2), U256::zero(), 0).is_err());
+        assert!(fee_ceiling(U256::one() << 255, U256::from(21_000), U256::zero(), 0).is_err());
+        assert!(fee_ceiling(U256::MAX, U256::one(), U256::one(), 1).is_err());
+    }
+}
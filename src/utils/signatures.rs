@@ -0,0 +1,167 @@
+//! Offline function signature database, used to decode calldata when no ABI is at hand.
+//!
+//! Only the signatures of widely deployed standards are bundled. A 4-byte selector can
+//! belong to more than one signature, so every match is decoded and reported.
+
+use ethers::abi::{Function, Token};
+
+use super::abi_args;
+
+/// Bundled function signatures, grouped by the standard or contract that defines them.
+/// Tuple components are unnamed, as the signature parser only accepts names at the top level
+const SIGNATURES: &[&str] = &[
+    // ERC-20 (and WETH)
+    "transfer(address to, uint256 amount)",
+    "transferFrom(address from, address to, uint256 amount)",
+    "approve(address spender, uint256 amount)",
+    "increaseAllowance(address spender, uint256 addedValue)",
+    "decreaseAllowance(address spender, uint256 subtractedValue)",
+    "mint(address to, uint256 amount)",
+    "burn(uint256 amount)",
+    "burnFrom(address account, uint256 amount)",
+    "deposit()",
+    "withdraw(uint256 amount)",
+    // ERC-2612
+    "permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s)",
+    // ERC-721
+    "safeTransferFrom(address from, address to, uint256 tokenId)",
+    "safeTransferFrom(address from, address to, uint256 tokenId, bytes data)",
+    "setApprovalForAll(address operator, bool approved)",
+    "safeMint(address to, uint256 tokenId)",
+    // ERC-1155
+    "safeTransferFrom(address from, address to, uint256 id, uint256 amount, bytes data)",
+    "safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] amounts, bytes data)",
+    // Ownable, Ownable2Step
+    "transferOwnership(address newOwner)",
+    "renounceOwnership()",
+    "acceptOwnership()",
+    // AccessControl
+    "grantRole(bytes32 role, address account)",
+    "revokeRole(bytes32 role, address account)",
+    "renounceRole(bytes32 role, address account)",
+    // Pausable
+    "pause()",
+    "unpause()",
+    // Safe
+    "execTransaction(address to, uint256 value, bytes data, uint8 operation, uint256 safeTxGas, uint256 baseGas, uint256 gasPrice, address gasToken, address refundReceiver, bytes signatures)",
+    "approveHash(bytes32 hashToApprove)",
+    "addOwnerWithThreshold(address owner, uint256 threshold)",
+    "removeOwner(address prevOwner, address owner, uint256 threshold)",
+    "swapOwner(address prevOwner, address oldOwner, address newOwner)",
+    "changeThreshold(uint256 threshold)",
+    "enableModule(address module)",
+    "disableModule(address prevModule, address module)",
+    "setGuard(address guard)",
+    "setFallbackHandler(address handler)",
+    "setup(address[] owners, uint256 threshold, address to, bytes data, address fallbackHandler, address paymentToken, uint256 payment, address paymentReceiver)",
+    "multiSend(bytes transactions)",
+    "createProxyWithNonce(address singleton, bytes initializer, uint256 saltNonce)",
+    // Proxies (ERC-1967, UUPS, transparent proxy admin, beacon)
+    "upgradeTo(address newImplementation)",
+    "upgradeToAndCall(address newImplementation, bytes data)",
+    "changeAdmin(address newAdmin)",
+    "upgrade(address proxy, address implementation)",
+    "upgradeAndCall(address proxy, address implementation, bytes data)",
+    "changeProxyAdmin(address proxy, address newAdmin)",
+    // Multicall3, and the multicall of OpenZeppelin and Uniswap
+    "aggregate((address,bytes)[] calls)",
+    "aggregate3((address,bool,bytes)[] calls)",
+    "aggregate3Value((address,bool,uint256,bytes)[] calls)",
+    "tryAggregate(bool requireSuccess, (address,bytes)[] calls)",
+    "blockAndAggregate((address,bytes)[] calls)",
+    "tryBlockAndAggregate(bool requireSuccess, (address,bytes)[] calls)",
+    "multicall(bytes[] data)",
+    // Permit2
+    "approve(address token, address spender, uint160 amount, uint48 expiration)",
+    "permit(address owner, ((address,uint160,uint48,uint48),address,uint256) permitSingle, bytes signature)",
+    "permit(address owner, ((address,uint160,uint48,uint48)[],address,uint256) permitBatch, bytes signature)",
+    "transferFrom(address from, address to, uint160 amount, address token)",
+    "permitTransferFrom(((address,uint256),uint256,uint256) permit, (address,uint256) transferDetails, address owner, bytes signature)",
+    "lockdown((address,address)[] approvals)",
+    "invalidateNonces(address token, address spender, uint48 newNonce)",
+    "invalidateUnorderedNonces(uint256 wordPos, uint256 mask)",
+];
+
+/// Bundled functions whose selector is `selector`
+pub fn lookup(selector: &[u8]) -> Vec<Function> {
+    lookup_in(SIGNATURES, selector)
+}
+
+/// Decode calldata with every bundled function matching its selector. A function only
+/// matches when its arguments decode and re-encode to exactly the given calldata.
+pub fn decode(data: &[u8]) -> Vec<(Function, Vec<Token>)> {
+    decode_in(SIGNATURES, data)
+}
+
+fn lookup_in(signatures: &[&str], selector: &[u8]) -> Vec<Function> {
+    signatures
+        .iter()
+        .map(|signature| abi_args::parse_signature(signature).expect("bundled signatures are valid"))
+        .filter(|function| function.short_signature()[..] == *selector)
+        .collect()
+}
+
+fn decode_in(signatures: &[&str], data: &[u8]) -> Vec<(Function, Vec<Token>)> {
+    let Some(selector) = data.get(..4) else { return Vec::new() };
+    lookup_in(signatures, selector)
+        .into_iter()
+        .filter_map(|function| {
+            let tokens = function.decode_input(&data[4..]).ok()?;
+            let encoded = function.encode_input(&tokens).ok()?;
+            (encoded == data).then_some((function, tokens))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{H160, U256};
+
+    #[test]
+    fn every_bundled_signature_parses_and_is_found_by_its_selector() {
+        for signature in SIGNATURES {
+            let function = abi_args::parse_signature(signature)
+                .unwrap_or_else(|e| panic!("{}: {}", signature, e));
+            let found = lookup(&function.short_signature());
+            assert!(
+                found.iter().any(|f| f.signature() == function.signature()),
+                "{} is not found by its selector",
+                signature
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_calldata_with_the_bundled_signature() {
+        let function = abi_args::parse_signature("transfer(address,uint256)").unwrap();
+        let tokens = vec![Token::Address(H160::repeat_byte(0x11)), Token::Uint(U256::from(100))];
+        let data = function.encode_input(&tokens).unwrap();
+
+        let matches = decode(&data);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0.name, "transfer");
+        assert_eq!(matches[0].1, tokens);
+
+        assert!(decode(&data[..data.len() - 1]).is_empty());
+        assert!(decode(&[0xde, 0xad, 0xbe, 0xef]).is_empty());
+        assert!(decode(&data[..3]).is_empty());
+    }
+
+    #[test]
+    fn lists_every_candidate_sharing_a_selector() {
+        // Same selector as transfer(address,uint256), with an argument layout that cannot
+        // decode transfer calldata
+        let signatures = ["transfer(address to, uint256 amount)", "many_msg_babbage(bytes1)", "transfer(address recipient, uint256 value)"];
+        let function = abi_args::parse_signature(signatures[0]).unwrap();
+        assert_eq!(abi_args::parse_signature(signatures[1]).unwrap().short_signature(), function.short_signature());
+
+        let data = function
+            .encode_input(&[Token::Address(H160::repeat_byte(0x11)), Token::Uint(U256::from(100))])
+            .unwrap();
+        assert_eq!(lookup_in(&signatures, &data[..4]).len(), 3);
+        let matches = decode_in(&signatures, &data);
+        let names: Vec<_> = matches.iter().map(|(function, _)| function.inputs[1].name.as_str()).collect();
+        assert_eq!(names, ["amount", "value"]);
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use ethers::{
+    abi::{Abi, ParamType, Token},
+    providers::{Http, Provider, RpcError},
+    types::{Bytes, U256},
+};
+
+use super::abi_args::format_token;
+use super::gas::CallRequest;
+
+/// Selector of `Error(string)`, emitted by `require(cond, "reason")` and `revert("reason")`
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of `Panic(uint256)`, emitted by failed assertions and checked arithmetic
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Result of a pre-flight `eth_call`
+pub enum Simulation {
+    Success,
+    /// The call reverted; holds the decoded reason
+    Reverted(String),
+}
+
+/// Simulate the transaction with `eth_call` against the pending block.
+///
+/// Revert data is decoded as `Error(string)`, `Panic(uint256)` or one of the custom
+/// errors declared in `abi`. Errors that are not reverts (e.g. an unreachable RPC)
+/// are returned as `Err`.
+pub async fn simulate(
+    provider: &Provider<Http>,
+    call: &CallRequest<'_>,
+    abi: Option<&Abi>,
+) -> Result<Simulation> {
+    let result: Result<Bytes, _> = provider
+        .request("eth_call", call.params())
+        .await;
+
+    match result {
+        Ok(_) => Ok(Simulation::Success),
+        Err(err) => {
+            let Some(response) = err.as_error_response() else {
+                return Err(err).context("eth_call simulation failed");
+            };
+            let reason = match response.as_revert_data() {
+                Some(revert_data) => decode_revert(&revert_data, abi),
+                // Not a revert, e.g. insufficient funds for value or gas
+                None => response.message.clone(),
+            };
+            Ok(Simulation::Reverted(reason))
+        }
+    }
+}
+
+/// Decode revert data into a readable message
+pub fn decode_revert(data: &[u8], abi: Option<&Abi>) -> String {
+    if data.is_empty() {
+        return "execution reverted without a reason".to_string();
+    }
+    if data.len() < 4 {
+        return format!("execution reverted with malformed data 0x{}", hex::encode(data));
+    }
+
+    let (selector, payload) = data.split_at(4);
+    if selector == ERROR_SELECTOR {
+        if let Ok(tokens) = ethers::abi::decode(&[ParamType::String], payload) {
+            if let Some(Token::String(reason)) = tokens.first() {
+                return format!("Error({:?})", reason);
+            }
+        }
+    }
+    if selector == PANIC_SELECTOR {
+        if let Ok(tokens) = ethers::abi::decode(&[ParamType::Uint(256)], payload) {
+            if let Some(Token::Uint(code)) = tokens.first() {
+                return format!("Panic(0x{:02x}): {}", code, panic_description(*code));
+            }
+        }
+    }
+
+    let custom = abi.and_then(|abi| {
+        abi.errors()
+            .find(|error| error.signature()[..4] == *selector)
+            .and_then(|error| {
+                let tokens = error.decode(payload).ok()?;
+                let args = error
+                    .inputs
+                    .iter()
+                    .zip(tokens.iter())
+                    .map(|(param, token)| {
+                        if param.name.is_empty() {
+                            format_token(token)
+                        } else {
+                            format!("{}: {}", param.name, format_token(token))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Some(format!("{}({})", error.name, args))
+            })
+    });
+
+    custom.unwrap_or_else(|| {
+        format!(
+            "unknown custom error 0x{} (not declared in the ABI), data: 0x{}",
+            hex::encode(selector),
+            hex::encode(data)
+        )
+    })
+}
+
+/// Meaning of the Solidity panic codes
+fn panic_description(code: U256) -> &'static str {
+    if code > U256::from(u8::MAX) {
+        return "unknown panic code";
+    }
+    match code.as_u32() {
+        0x00 => "generic compiler panic",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop() on an empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to an uninitialized function pointer",
+        _ => "unknown panic code",
+    }
+}
@@ -0,0 +1,410 @@
+use anyhow::{Context, Result};
+use ethers::{
+    types::{transaction::eip2930::AccessList, Address, Bytes, Signature, H256, U256},
+    utils::{
+        keccak256,
+        rlp::{Rlp, RlpStream},
+    },
+};
+
+use crate::types::prepare_output::BlobSidecar;
+
+/// EIP-2718 type byte of blob transactions
+pub const BLOB_TX_TYPE: u8 = 0x03;
+
+/// EIP-2718 type byte of set-code transactions
+pub const SET_CODE_TX_TYPE: u8 = 0x04;
+
+/// Prefix of EIP-7702 authorization signing payloads
+const AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// Code prefix marking a delegated account; followed by the 20-byte delegate address
+pub const DELEGATION_DESIGNATOR_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// An EIP-4844 blob transaction (type 3), which ethers cannot build
+#[derive(Debug, Clone)]
+pub struct BlobTransaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub access_list: AccessList,
+    pub max_fee_per_blob_gas: U256,
+    pub blob_versioned_hashes: Vec<H256>,
+}
+
+impl BlobTransaction {
+    /// Hash signed by the sender: `keccak256(0x03 || rlp(fields))`
+    pub fn sighash(&self) -> H256 {
+        let mut stream = RlpStream::new_list(11);
+        self.append_fields(&mut stream);
+        H256::from(keccak256(typed_envelope(BLOB_TX_TYPE, &stream.out())))
+    }
+
+    /// Signed transaction as included in blocks; its hash is the transaction hash
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        let mut stream = RlpStream::new_list(14);
+        self.append_fields(&mut stream);
+        append_signature(&mut stream, signature);
+        typed_envelope(BLOB_TX_TYPE, &stream.out()).into()
+    }
+
+    /// Signed transaction wrapped with its blob sidecar, as sent to `eth_sendRawTransaction`.
+    ///
+    /// Version 0 is the original EIP-4844 wrapper `[tx, blobs, commitments, proofs]`;
+    /// version 1 (EIP-7594) adds the version and carries cell proofs.
+    pub fn rlp_network(&self, signature: &Signature, sidecar: &BlobSidecar) -> Bytes {
+        let versioned = sidecar.wrapper_version != 0;
+        let mut stream = RlpStream::new_list(if versioned { 5 } else { 4 });
+        stream.begin_list(14);
+        self.append_fields(&mut stream);
+        append_signature(&mut stream, signature);
+        if versioned {
+            stream.append(&sidecar.wrapper_version);
+        }
+        append_bytes_list(&mut stream, &sidecar.blobs);
+        append_bytes_list(&mut stream, &sidecar.commitments);
+        append_bytes_list(&mut stream, &sidecar.proofs);
+        typed_envelope(BLOB_TX_TYPE, &stream.out()).into()
+    }
+
+    fn append_fields(&self, stream: &mut RlpStream) {
+        stream.append(&self.chain_id);
+        stream.append(&self.nonce);
+        stream.append(&self.max_priority_fee_per_gas);
+        stream.append(&self.max_fee_per_gas);
+        stream.append(&self.gas_limit);
+        stream.append(&self.to);
+        stream.append(&self.value);
+        stream.append(&self.data.as_ref());
+        stream.append(&self.access_list);
+        stream.append(&self.max_fee_per_blob_gas);
+        stream.append_list::<H256, H256>(&self.blob_versioned_hashes);
+    }
+}
+
+/// Hash an EIP-7702 authority signs: `keccak256(0x05 || rlp([chain_id, address, nonce]))`
+pub fn authorization_sighash(chain_id: u64, address: Address, nonce: u64) -> H256 {
+    let mut stream = RlpStream::new_list(3);
+    stream.append(&chain_id);
+    stream.append(&address);
+    stream.append(&nonce);
+    H256::from(keccak256(typed_envelope(AUTHORIZATION_MAGIC, &stream.out())))
+}
+
+/// A signed EIP-7702 authorization tuple
+#[derive(Debug, Clone)]
+pub struct SignedAuthorization {
+    pub chain_id: u64,
+    pub address: Address,
+    pub nonce: u64,
+    pub signature: Signature,
+}
+
+impl SignedAuthorization {
+    fn append(&self, stream: &mut RlpStream) {
+        stream.begin_list(6);
+        stream.append(&self.chain_id);
+        stream.append(&self.address);
+        stream.append(&self.nonce);
+        append_signature(stream, &self.signature);
+    }
+}
+
+/// An EIP-7702 set-code transaction (type 4), which ethers cannot build
+#[derive(Debug, Clone)]
+pub struct SetCodeTransaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub access_list: AccessList,
+    pub authorization_list: Vec<SignedAuthorization>,
+}
+
+impl SetCodeTransaction {
+    /// Hash signed by the sender: `keccak256(0x04 || rlp(fields))`
+    pub fn sighash(&self) -> H256 {
+        let mut stream = RlpStream::new_list(10);
+        self.append_fields(&mut stream);
+        H256::from(keccak256(typed_envelope(SET_CODE_TX_TYPE, &stream.out())))
+    }
+
+    /// Signed transaction, as sent to `eth_sendRawTransaction`
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        let mut stream = RlpStream::new_list(13);
+        self.append_fields(&mut stream);
+        append_signature(&mut stream, signature);
+        typed_envelope(SET_CODE_TX_TYPE, &stream.out()).into()
+    }
+
+    fn append_fields(&self, stream: &mut RlpStream) {
+        stream.append(&self.chain_id);
+        stream.append(&self.nonce);
+        stream.append(&self.max_priority_fee_per_gas);
+        stream.append(&self.max_fee_per_gas);
+        stream.append(&self.gas_limit);
+        stream.append(&self.to);
+        stream.append(&self.value);
+        stream.append(&self.data.as_ref());
+        stream.append(&self.access_list);
+        stream.begin_list(self.authorization_list.len());
+        for authorization in &self.authorization_list {
+            authorization.append(stream);
+        }
+    }
+}
+
+/// A signed blob or set-code transaction read back from its raw encoding
+#[derive(Debug, Clone)]
+pub struct DecodedTransaction {
+    pub tx_type: u8,
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub access_list: AccessList,
+    /// Type 3 only
+    pub max_fee_per_blob_gas: Option<U256>,
+    pub blob_versioned_hashes: Vec<H256>,
+    /// Type 4 only
+    pub authorization_list: Vec<SignedAuthorization>,
+    /// Hash signed by the sender
+    pub sighash: H256,
+    pub signature: Signature,
+    /// Transaction hash; for a blob transaction, of the transaction without its sidecar
+    pub hash: H256,
+}
+
+/// Decode a signed type-3 or type-4 transaction, as written by `rlp_network` and
+/// `rlp_signed`. A blob transaction may come with or without its sidecar
+pub fn decode_signed(raw: &[u8]) -> Result<DecodedTransaction> {
+    let (&tx_type, payload) = raw.split_first().context("Empty transaction")?;
+    let fields = match tx_type {
+        BLOB_TX_TYPE => 11,
+        SET_CODE_TX_TYPE => 10,
+        _ => anyhow::bail!("Not a blob or set-code transaction (type {})", tx_type),
+    };
+
+    let rlp = Rlp::new(payload);
+    if rlp.payload_info()?.total() != payload.len() {
+        anyhow::bail!("Trailing bytes after the transaction");
+    }
+    // The network form wraps the signed transaction in a list with the sidecar
+    let tx = if tx_type == BLOB_TX_TYPE && rlp.at(0)?.is_list() { rlp.at(0)? } else { rlp };
+    if tx.item_count()? != fields + 3 {
+        anyhow::bail!("Expected {} fields in a type-{} transaction, found {}", fields + 3, tx_type, tx.item_count()?);
+    }
+
+    // The sender signed the same fields without the signature
+    let mut unsigned = RlpStream::new_list(fields);
+    for i in 0..fields {
+        unsigned.append_raw(tx.at(i)?.as_raw(), 1);
+    }
+    let sighash = H256::from(keccak256(typed_envelope(tx_type, &unsigned.out())));
+    let hash = H256::from(keccak256(typed_envelope(tx_type, tx.as_raw())));
+
+    let authorization_list = if tx_type == SET_CODE_TX_TYPE {
+        tx.at(9)?
+            .iter()
+            .map(|item| {
+                Ok(SignedAuthorization {
+                    chain_id: item.val_at(0)?,
+                    address: item.val_at(1)?,
+                    nonce: item.val_at(2)?,
+                    signature: decode_signature(&item, 3)?,
+                })
+            })
+            .collect::<Result<_>>()?
+    } else {
+        Vec::new()
+    };
+    let is_blob = tx_type == BLOB_TX_TYPE;
+
+    Ok(DecodedTransaction {
+        tx_type,
+        chain_id: tx.val_at(0)?,
+        nonce: tx.val_at(1)?,
+        max_priority_fee_per_gas: tx.val_at(2)?,
+        max_fee_per_gas: tx.val_at(3)?,
+        gas_limit: tx.val_at(4)?,
+        to: tx.val_at(5)?,
+        value: tx.val_at(6)?,
+        data: tx.val_at::<Vec<u8>>(7)?.into(),
+        access_list: tx.val_at(8)?,
+        max_fee_per_blob_gas: if is_blob { Some(tx.val_at(9)?) } else { None },
+        blob_versioned_hashes: if is_blob { tx.list_at(10)? } else { Vec::new() },
+        authorization_list,
+        sighash,
+        signature: decode_signature(&tx, fields)?,
+        hash,
+    })
+}
+
+/// Read `y_parity, r, s` starting at item `index`, as a signature with `v` of 27/28
+fn decode_signature(rlp: &Rlp, index: usize) -> Result<Signature> {
+    let y_parity: u64 = rlp.val_at(index)?;
+    if y_parity > 1 {
+        anyhow::bail!("Invalid signature y-parity {}", y_parity);
+    }
+    Ok(Signature {
+        r: rlp.val_at(index + 1)?,
+        s: rlp.val_at(index + 2)?,
+        v: y_parity + 27,
+    })
+}
+
+/// Append `y_parity, r, s`; `sign_hash` returns `v` as 27/28
+fn append_signature(stream: &mut RlpStream, signature: &Signature) {
+    stream.append(&(signature.v - 27));
+    stream.append(&signature.r);
+    stream.append(&signature.s);
+}
+
+fn append_bytes_list(stream: &mut RlpStream, items: &[Bytes]) {
+    stream.begin_list(items.len());
+    for item in items {
+        stream.append(&item.as_ref());
+    }
+}
+
+/// Prefix an RLP payload with its EIP-2718 transaction type
+fn typed_envelope(tx_type: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 1);
+    out.push(tx_type);
+    out.extend_from_slice(payload);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+
+    fn wallet() -> LocalWallet {
+        "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap()
+    }
+
+    fn blob_tx() -> BlobTransaction {
+        let mut versioned_hash = H256::zero();
+        versioned_hash.0[0] = 0x01;
+        BlobTransaction {
+            chain_id: 1,
+            nonce: 0,
+            max_priority_fee_per_gas: U256::zero(),
+            max_fee_per_gas: U256::zero(),
+            gas_limit: 0,
+            to: Address::repeat_byte(0x22),
+            value: U256::zero(),
+            data: Bytes::new(),
+            access_list: AccessList::default(),
+            max_fee_per_blob_gas: U256::zero(),
+            blob_versioned_hashes: vec![versioned_hash],
+        }
+    }
+
+    #[test]
+    fn authorization_sighash_matches_hand_encoded_tuple() {
+        // 0x05 || rlp([1, 0x1111…11, 0])
+        let mut payload = vec![0x05, 0xd7, 0x01, 0x94];
+        payload.extend_from_slice(&[0x11; 20]);
+        payload.push(0x80);
+        let expected = H256::from(keccak256(&payload));
+        assert_eq!(authorization_sighash(1, Address::repeat_byte(0x11), 0), expected);
+    }
+
+    #[test]
+    fn blob_sighash_matches_hand_encoded_fields() {
+        // 0x03 || rlp([1, 0, 0, 0, 0, to, 0, "", [], 0, [versioned_hash]])
+        let mut payload = vec![0x03, 0xf8, 0x40, 0x01, 0x80, 0x80, 0x80, 0x80, 0x94];
+        payload.extend_from_slice(&[0x22; 20]);
+        payload.extend_from_slice(&[0x80, 0x80, 0xc0, 0x80, 0xe1, 0xa0, 0x01]);
+        payload.extend_from_slice(&[0x00; 31]);
+        assert_eq!(blob_tx().sighash(), H256::from(keccak256(&payload)));
+    }
+
+    #[test]
+    fn signed_blob_transaction_decodes_with_and_without_sidecar() {
+        let wallet = wallet();
+        let tx = blob_tx();
+        let signature = wallet.sign_hash(tx.sighash()).unwrap();
+        let raw = tx.rlp_signed(&signature);
+
+        let decoded = decode_signed(&raw).unwrap();
+        assert_eq!(decoded.tx_type, BLOB_TX_TYPE);
+        assert_eq!(decoded.sighash, tx.sighash());
+        assert_eq!(decoded.hash, H256::from(keccak256(&raw)));
+        assert_eq!(decoded.blob_versioned_hashes, tx.blob_versioned_hashes);
+        assert_eq!(decoded.signature.recover(decoded.sighash).unwrap(), wallet.address());
+
+        for wrapper_version in [0, 1] {
+            let sidecar = BlobSidecar {
+                wrapper_version,
+                blobs: vec![Bytes::from(vec![0xaa; 4])],
+                commitments: vec![Bytes::from(vec![0xbb; 48])],
+                proofs: vec![Bytes::from(vec![0xcc; 48])],
+            };
+            let network = decode_signed(&tx.rlp_network(&signature, &sidecar)).unwrap();
+            assert_eq!(network.hash, decoded.hash);
+            assert_eq!(network.sighash, decoded.sighash);
+        }
+
+        assert!(decode_signed(&[raw.as_ref(), &[0x00]].concat()).is_err());
+    }
+
+    #[test]
+    fn signed_set_code_transaction_recovers_sender_and_authority() {
+        let wallet = wallet();
+        let delegate = Address::repeat_byte(0x33);
+        let authorization = SignedAuthorization {
+            chain_id: 1,
+            address: delegate,
+            nonce: 6,
+            signature: wallet.sign_hash(authorization_sighash(1, delegate, 6)).unwrap(),
+        };
+        let tx = SetCodeTransaction {
+            chain_id: 1,
+            nonce: 5,
+            max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+            max_fee_per_gas: U256::from(30_000_000_000u64),
+            gas_limit: 100_000,
+            to: wallet.address(),
+            value: U256::zero(),
+            data: Bytes::from(vec![0x12, 0x34]),
+            access_list: AccessList::default(),
+            authorization_list: vec![authorization],
+        };
+        let signature = wallet.sign_hash(tx.sighash()).unwrap();
+        let raw = tx.rlp_signed(&signature);
+
+        let decoded = decode_signed(&raw).unwrap();
+        assert_eq!(decoded.tx_type, SET_CODE_TX_TYPE);
+        assert_eq!(decoded.nonce, 5);
+        assert_eq!(decoded.data, tx.data);
+        assert_eq!(decoded.max_fee_per_blob_gas, None);
+        assert_eq!(decoded.signature.recover(decoded.sighash).unwrap(), wallet.address());
+
+        let [authorization] = &decoded.authorization_list[..] else { panic!("expected one authorization") };
+        assert_eq!((authorization.chain_id, authorization.address, authorization.nonce), (1, delegate, 6));
+        let authority = authorization.signature.recover(authorization_sighash(1, delegate, 6)).unwrap();
+        assert_eq!(authority, wallet.address());
+    }
+
+    #[test]
+    fn rejects_other_transaction_types() {
+        assert!(decode_signed(&[]).is_err());
+        assert!(decode_signed(&[0x02, 0xc0]).is_err());
+    }
+}
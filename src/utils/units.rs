@@ -0,0 +1,65 @@
+use anyhow::Result;
+use ethers::{
+    types::U256,
+    utils::{format_units, parse_units},
+};
+
+/// Parse an amount with an optional unit suffix (`wei`, `gwei` or `ether`).
+///
+/// Amounts without a suffix are read in `default_unit`, e.g. `parse_amount("1.5", "gwei")`.
+pub fn parse_amount(input: &str, default_unit: &str) -> Result<U256> {
+    let trimmed = input.trim();
+    let split = trimmed
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(trimmed.len());
+    let (amount, unit) = trimmed.split_at(split);
+    let amount = amount.trim();
+    let unit = match unit.trim().to_lowercase().as_str() {
+        "" => default_unit.to_string(),
+        u @ ("wei" | "gwei" | "ether") => u.to_string(),
+        u => anyhow::bail!("Unknown unit '{}' in '{}' (expected wei, gwei or ether)", u, input),
+    };
+
+    if amount.is_empty() || amount.starts_with('-') {
+        anyhow::bail!("Invalid amount: '{}'", input);
+    }
+    let parsed = parse_units(amount, unit.as_str())
+        .map_err(|e| anyhow::anyhow!("Invalid amount '{}': {}", input, e))?;
+    Ok(parsed.into())
+}
+
+/// Parse a native-token amount in wei: decimal, `0x` hex, or with a unit suffix (`1.5ether`)
+pub fn parse_wei(input: &str) -> Result<U256> {
+    let trimmed = input.trim();
+    if let Some(hex) = trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")) {
+        if hex.is_empty() || hex.len() > 64 {
+            anyhow::bail!("Invalid hex amount: '{}'", input);
+        }
+        return U256::from_str_radix(hex, 16)
+            .map_err(|_| anyhow::anyhow!("Invalid hex amount: '{}'", input));
+    }
+    parse_amount(trimmed, "wei")
+}
+
+/// Parse a fee given in gwei unless another unit is specified (e.g. `"1.5"`, `"100wei"`)
+pub fn parse_gwei(input: &str) -> Result<U256> {
+    parse_amount(input, "gwei")
+}
+
+/// Format a wei amount in gwei without trailing zeros (e.g. `1.5`)
+pub fn format_gwei(wei: U256) -> String {
+    trim_decimal(format_units(wei, "gwei").unwrap_or_else(|_| wei.to_string()))
+}
+
+/// Format a wei amount in ether (or another 18-decimal native unit) without trailing zeros
+pub fn format_ether(wei: U256) -> String {
+    trim_decimal(format_units(wei, "ether").unwrap_or_else(|_| wei.to_string()))
+}
+
+fn trim_decimal(s: String) -> String {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        s
+    }
+}